@@ -1,29 +1,289 @@
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::ItemFn;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{
+    meta::ParseNestedMeta, Attribute, Data, DeriveInput, Expr, ExprArray, ExprLit, Fields, FnArg,
+    ItemFn, Lit, LitStr, Meta, Pat, PatType, ReturnType, Type,
+};
 
+/// Options for `#[slash_command(...)]`
+#[derive(Default)]
+struct SlashCommandOptions {
+    name: Option<LitStr>,
+    aliases: Vec<LitStr>,
+    description: Option<LitStr>,
+    usage: Option<LitStr>,
+}
+
+impl SlashCommandOptions {
+    fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("name") {
+            self.name = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("aliases") {
+            let aliases: ExprArray = meta.value()?.parse()?;
+            for alias in aliases.elems {
+                match alias {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(alias),
+                        ..
+                    }) => self.aliases.push(alias),
+                    alias => {
+                        return Err(syn::Error::new_spanned(alias, "aliases need to be strings"))
+                    }
+                }
+            }
+        } else if meta.path.is_ident("description") {
+            self.description = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("usage") {
+            self.usage = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error(
+                "unknown option, expected one of `name`, `aliases`, `description` or `usage`",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// How a message action matches `action_id`s
+enum ActionMatch {
+    Exact(LitStr),
+    Prefix(LitStr),
+    Regex(LitStr),
+}
+
+/// Options for `#[message_action(...)]`
+#[derive(Default)]
+struct MessageActionOptions {
+    action: Option<ActionMatch>,
+    block_id: Option<LitStr>,
+}
+
+impl MessageActionOptions {
+    fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        let action = if meta.path.is_ident("action_id") {
+            ActionMatch::Exact(meta.value()?.parse()?)
+        } else if meta.path.is_ident("action_prefix") {
+            ActionMatch::Prefix(meta.value()?.parse()?)
+        } else if meta.path.is_ident("action_regex") {
+            let regex: LitStr = meta.value()?.parse()?;
+            // a typo should stop the build, not the client when it starts
+            if let Err(err) = regex::Regex::new(&regex.value()) {
+                return Err(syn::Error::new_spanned(
+                    &regex,
+                    format!("invalid action_regex: {}", err),
+                ));
+            }
+            ActionMatch::Regex(regex)
+        } else if meta.path.is_ident("block_id") {
+            self.block_id = Some(meta.value()?.parse()?);
+            return Ok(());
+        } else {
+            return Err(meta.error(
+                "unknown option, expected one of `action_id`, `action_prefix`, `action_regex` or `block_id`",
+            ));
+        };
+
+        if self.action.is_some() {
+            return Err(meta
+                .error("only one of `action_id`, `action_prefix` or `action_regex` can be used"));
+        }
+
+        self.action = Some(action);
+        Ok(())
+    }
+}
+
+/// Options for `#[global_shortcut(...)]` and `#[message_shortcut(...)]`
+#[derive(Default)]
+struct ShortcutOptions {
+    callback_id: Option<LitStr>,
+}
+
+impl ShortcutOptions {
+    fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("callback_id") {
+            self.callback_id = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("unknown option, expected `callback_id`"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Turns an `async fn` into a function returning a `SlashCommand`.
+///
+/// The first parameter is the `InteractionContext`, and every parameter after it is parsed from the
+/// text of the command. Doc comments are used as the description in the help listing.
+///
+/// ```ignore
+/// /// Reminds someone about something
+/// #[slash_command(name = "remind-me", aliases = ["r"])]
+/// async fn remind(ctx: InteractionContext, after: Duration, note: Rest) { ... }
+/// ```
 #[proc_macro_attribute]
 pub fn slash_command(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut options = SlashCommandOptions::default();
+    let parser = syn::meta::parser(|meta| options.parse(meta));
+    syn::parse_macro_input!(attr with parser);
+
     // Construct a representation of Rust code as a syntax tree
     // that we can manipulate
     let function = syn::parse_macro_input!(item as syn::ItemFn);
 
+    if let Err(err) = check_signature(&function, "slash commands") {
+        return err.to_compile_error().into();
+    }
+
     let name = &function.sig.ident;
     let content = &function.block;
+    // keep doc comments, lints and whatever else is on the function
+    let attrs = &function.attrs;
+    let vis = &function.vis;
+    let output = return_type(&function);
+
+    let command = match &options.name {
+        Some(command) => command.value(),
+        None => name.to_string(),
+    };
+    let aliases = &options.aliases;
+
+    let description = match options
+        .description
+        .as_ref()
+        .map(LitStr::value)
+        .or_else(|| doc_comment(&function.attrs))
+    {
+        Some(description) => {
+            quote!(::std::option::Option::Some(::std::string::String::from(#description)))
+        }
+        None => quote!(::std::option::Option::None),
+    };
+
+    // the first parameter is the context, everything after it is parsed from the command text
+    let mut inputs = function.sig.inputs.iter();
+    let (ctx, ctx_type) = match inputs.next() {
+        Some(FnArg::Typed(PatType { pat, ty, .. })) => (pat, ty),
+        _ => {
+            return syn::Error::new_spanned(
+                &function.sig,
+                "slash commands need to take an `InteractionContext` as their first parameter",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+
+    let mut patterns = Vec::new();
+    let mut types = Vec::new();
+    let mut names = Vec::new();
+    for input in inputs {
+        let PatType { pat, ty, .. } = match input {
+            FnArg::Typed(input) => input,
+            FnArg::Receiver(receiver) => {
+                return syn::Error::new_spanned(receiver, "slash commands can't take `self`")
+                    .to_compile_error()
+                    .into()
+            }
+        };
+
+        names.push(match pat.as_ref() {
+            Pat::Ident(ident) => ident.ident.to_string(),
+            _ => quote!(#pat).to_string(),
+        });
+        patterns.push(pat);
+        types.push(ty);
+    }
+
+    let usage = match &options.usage {
+        Some(usage) => quote!(::std::string::String::from(#usage)),
+        None => quote! {
+            let arguments: ::std::vec::Vec<::std::string::String> = ::std::vec![
+                #(::crow::__private::usage_of::<#types>(#names)),*
+            ];
+            arguments.join(" ")
+        },
+    };
+
+    // everything the generated code declares itself is unreachable from the handler, so its
+    // parameters can have any name
+    let span = Span::mixed_site();
+    let usage_fn = format_ident!("__crow_usage", span = span);
+    let inner = format_ident!("__crow_inner", span = span);
+    let values: Vec<_> = (0..patterns.len())
+        .map(|i| format_ident!("__crow_arg_{}", i, span = span))
+        .collect();
+    let (ctx_arg, text, arguments, future, response, value, err) = (
+        format_ident!("__crow_ctx", span = span),
+        format_ident!("__crow_text", span = span),
+        format_ident!("__crow_arguments", span = span),
+        format_ident!("__crow_future", span = span),
+        format_ident!("__crow_response", span = span),
+        format_ident!("__crow_value", span = span),
+        format_ident!("__crow_err", span = span),
+    );
 
     // Build the trait implementation
 
     let gen = quote! {
-        fn #name() -> crate::client::interactions::SlashCommand {
-            fn inner(ctx: InteractionContext<'_>, text: String, user: User, channel: Channel) -> BoxFuture<'_, ()> {
-                async move {
-                    #content
-                }.boxed()
+        #(#attrs)*
+        #vis fn #name() -> ::crow::__private::SlashCommand {
+            fn #usage_fn() -> ::std::string::String {
+                #usage
+            }
+
+            fn #inner(
+                #ctx_arg: ::crow::__private::InteractionContext,
+                #text: ::std::string::String,
+            ) -> ::crow::__private::BoxFuture<'static, ::crow::__private::HandlerResult> {
+                let #future = async move {
+                    let mut #arguments = ::crow::__private::Arguments::new(&#text);
+
+                    #(
+                        let #values: #types =
+                            match <#types as ::crow::__private::Argument>::parse(&mut #arguments) {
+                                ::std::result::Result::Ok(#value) => #value,
+                                ::std::result::Result::Err(#err) => {
+                                    ::crow::__private::report_usage_error(
+                                        &#ctx_arg,
+                                        &#usage_fn(),
+                                        ::std::format!("{}: {}", #names, #err),
+                                    )
+                                    .await;
+                                    return ::std::result::Result::Ok(::std::option::Option::None);
+                                }
+                            };
+                    )*
+
+                    if let ::std::result::Result::Err(#err) = #arguments.finish() {
+                        ::crow::__private::report_usage_error(
+                            &#ctx_arg,
+                            &#usage_fn(),
+                            ::std::string::ToString::to_string(&#err),
+                        )
+                        .await;
+                        return ::std::result::Result::Ok(::std::option::Option::None);
+                    }
+
+                    // the handler's own bindings only start here, after the last use of ours
+                    let #ctx: #ctx_type = #ctx_arg;
+                    #(let #patterns: #types = #values;)*
+                    let #response: #output = async move #content.await;
+                    ::crow::__private::IntoResponse::into_response(#response)
+                };
+
+                ::crow::__private::FutureExt::boxed(#future)
             }
 
-            crate::client::interactions::SlashCommand {
-                command: stringify!(#name).to_string(),
-                execute: inner,
+            ::crow::__private::SlashCommand {
+                command: ::std::string::String::from(#command),
+                aliases: ::std::vec![#(::std::string::String::from(#aliases)),*],
+                description: #description,
+                usage: #usage_fn(),
+                execute: ::std::option::Option::Some(#inner),
+                subcommands: ::std::vec::Vec::new(),
             }
         }
     };
@@ -31,31 +291,371 @@ pub fn slash_command(attr: TokenStream, item: TokenStream) -> TokenStream {
     gen.into()
 }
 
+/// Turns an `async fn` into a function returning a `MessageAction`.
+///
+/// The function takes the context and the `BlockAction` that was used. The older form, taking the
+/// context, the user who clicked, their username, their display name and the channel, works too.
+/// The action ID defaults to the name of the function, and can be matched by prefix or regex instead:
+///
+/// ```ignore
+/// #[message_action(action_prefix = "vote:", block_id = "poll")]
+/// async fn vote(ctx: InteractionContext, action: BlockAction) { ... }
+/// ```
 #[proc_macro_attribute]
 pub fn message_action(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut options = MessageActionOptions::default();
+    let parser = syn::meta::parser(|meta| options.parse(meta));
+    syn::parse_macro_input!(attr with parser);
+
     // Construct a representation of Rust code as a syntax tree
     // that we can manipulate
     let function = syn::parse_macro_input!(item as syn::ItemFn);
 
+    if let Err(err) = check_signature(&function, "message actions") {
+        return err.to_compile_error().into();
+    }
+
     let name = &function.sig.ident;
     let content = &function.block;
+    // keep doc comments, lints and whatever else is on the function
+    let attrs = &function.attrs;
+    let vis = &function.vis;
+    let output = return_type(&function);
+
+    let action = match &options.action {
+        Some(ActionMatch::Exact(id)) => {
+            quote!(::crow::__private::ActionId::Exact(::std::string::String::from(#id)))
+        }
+        Some(ActionMatch::Prefix(prefix)) => {
+            quote!(::crow::__private::ActionId::Prefix(::std::string::String::from(#prefix)))
+        }
+        Some(ActionMatch::Regex(regex)) => quote!(::crow::__private::ActionId::Regex(
+            ::crow::__private::Regex::new(#regex).expect("action_regex is checked when compiling")
+        )),
+        None => {
+            let id = name.to_string();
+            quote!(::crow::__private::ActionId::Exact(::std::string::String::from(#id)))
+        }
+    };
+    let block_id = match &options.block_id {
+        Some(block_id) => {
+            quote!(::std::option::Option::Some(::std::string::String::from(#block_id)))
+        }
+        None => quote!(::std::option::Option::None),
+    };
+
+    let mut patterns = Vec::new();
+    let mut types = Vec::new();
+    for input in &function.sig.inputs {
+        match input {
+            FnArg::Typed(PatType { pat, ty, .. }) => {
+                patterns.push(pat);
+                types.push(ty);
+            }
+            FnArg::Receiver(receiver) => {
+                return syn::Error::new_spanned(receiver, "message actions can't take `self`")
+                    .to_compile_error()
+                    .into()
+            }
+        }
+    }
+
+    let bind = match patterns.len() {
+        2 => quote! {
+            let (#(#patterns),*): (#(#types),*) = (__ctx, __action);
+        },
+        // the context goes last here, so the user and channel can be cloned out of it first
+        5 => {
+            let ctx_pattern = patterns.remove(0);
+            let ctx_type = types.remove(0);
+            quote! {
+                let (#(#patterns,)* #ctx_pattern): (#(#types,)* #ctx_type) = (
+                    __ctx.user.clone(),
+                    __action.username,
+                    __action.display_name,
+                    __ctx.channel.clone(),
+                    __ctx,
+                );
+            }
+        }
+        _ => {
+            return syn::Error::new_spanned(
+                &function.sig.inputs,
+                "message actions need to take `(ctx: InteractionContext, action: BlockAction)`",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
 
     // Build the trait implementation
 
     let gen = quote! {
-        fn #name() -> crate::client::interactions::MessageAction {
-            fn inner(ctx: InteractionContext<'_>, user: User, name: String, display_name: String, channel: Channel) -> BoxFuture<'_, ()> {
-                async move {
-                    #content
-                }.boxed()
+        #(#attrs)*
+        #vis fn #name() -> ::crow::__private::MessageAction {
+            fn __inner(
+                __ctx: ::crow::__private::InteractionContext,
+                __action: ::crow::__private::BlockAction,
+            ) -> ::crow::__private::BoxFuture<'static, ::crow::__private::HandlerResult> {
+                let future = async move {
+                    #bind
+                    let __response: #output = async move #content.await;
+                    ::crow::__private::IntoResponse::into_response(__response)
+                };
+
+                ::crow::__private::FutureExt::boxed(future)
+            }
+
+            ::crow::__private::MessageAction {
+                action: #action,
+                block_id: #block_id,
+                execute: __inner,
+            }
+        }
+    };
+
+    gen.into()
+}
+
+/// Turns an `async fn` into a function returning a `GlobalShortcut`.
+///
+/// The function only takes the context. The callback ID defaults to the name of the function.
+///
+/// ```ignore
+/// #[global_shortcut(callback_id = "new_ticket")]
+/// async fn new_ticket(ctx: InteractionContext) -> Result<(), SlackError> { ... }
+/// ```
+#[proc_macro_attribute]
+pub fn global_shortcut(attr: TokenStream, item: TokenStream) -> TokenStream {
+    shortcut(
+        attr,
+        item,
+        "global shortcuts",
+        "`(ctx: InteractionContext)`",
+        syn::parse_quote!(::crow::__private::GlobalShortcut),
+        &[],
+    )
+}
+
+/// Turns an `async fn` into a function returning a `MessageShortcut`.
+///
+/// The function takes the context and the `ShortcutMessage` it was used on. The callback ID
+/// defaults to the name of the function.
+///
+/// ```ignore
+/// #[message_shortcut(callback_id = "save_message")]
+/// async fn save(ctx: InteractionContext, message: ShortcutMessage) { ... }
+/// ```
+#[proc_macro_attribute]
+pub fn message_shortcut(attr: TokenStream, item: TokenStream) -> TokenStream {
+    shortcut(
+        attr,
+        item,
+        "message shortcuts",
+        "`(ctx: InteractionContext, message: ShortcutMessage)`",
+        syn::parse_quote!(::crow::__private::MessageShortcut),
+        &[syn::parse_quote!(::crow::__private::ShortcutMessage)],
+    )
+}
+
+/// Builds a shortcut whose handler takes the context and then one value of each of `extra`
+fn shortcut(
+    attr: TokenStream,
+    item: TokenStream,
+    kind: &str,
+    signature: &str,
+    shortcut: syn::Path,
+    extra: &[Type],
+) -> TokenStream {
+    let mut options = ShortcutOptions::default();
+    let parser = syn::meta::parser(|meta| options.parse(meta));
+    syn::parse_macro_input!(attr with parser);
+
+    let function = syn::parse_macro_input!(item as syn::ItemFn);
+
+    if let Err(err) = check_signature(&function, kind) {
+        return err.to_compile_error().into();
+    }
+
+    let name = &function.sig.ident;
+    let content = &function.block;
+    let attrs = &function.attrs;
+    let vis = &function.vis;
+    let output = return_type(&function);
+
+    let callback_id = match &options.callback_id {
+        Some(callback_id) => callback_id.value(),
+        None => name.to_string(),
+    };
+
+    let mut patterns = Vec::new();
+    let mut types = Vec::new();
+    for input in &function.sig.inputs {
+        match input {
+            FnArg::Typed(PatType { pat, ty, .. }) => {
+                patterns.push(pat);
+                types.push(ty);
+            }
+            FnArg::Receiver(receiver) => {
+                return syn::Error::new_spanned(receiver, format!("{} can't take `self`", kind))
+                    .to_compile_error()
+                    .into()
             }
+        }
+    }
+
+    if patterns.len() != extra.len() + 1 {
+        return syn::Error::new_spanned(
+            &function.sig.inputs,
+            format!("{} need to take {}", kind, signature),
+        )
+        .to_compile_error()
+        .into();
+    }
 
-            crate::client::interactions::MessageAction {
-                action: stringify!(#name).to_string(),
-                execute: inner,
+    let arguments: Vec<_> = (0..extra.len())
+        .map(|i| syn::Ident::new(&format!("__arg{}", i), name.span()))
+        .collect();
+
+    let gen = quote! {
+        #(#attrs)*
+        #vis fn #name() -> #shortcut {
+            fn __inner(
+                __ctx: ::crow::__private::InteractionContext,
+                #(#arguments: #extra),*
+            ) -> ::crow::__private::BoxFuture<'static, ::crow::__private::HandlerResult> {
+                let future = async move {
+                    let (#(#patterns,)*): (#(#types,)*) = (__ctx, #(#arguments,)*);
+                    let __response: #output = async move #content.await;
+                    ::crow::__private::IntoResponse::into_response(__response)
+                };
+
+                ::crow::__private::FutureExt::boxed(future)
             }
+
+            #shortcut::new(#callback_id, __inner)
         }
     };
 
     gen.into()
 }
+
+/// The type the handler returns, which has to implement `IntoResponse`
+fn return_type(function: &ItemFn) -> Type {
+    match &function.sig.output {
+        ReturnType::Default => syn::parse_quote!(()),
+        ReturnType::Type(_, ty) => (**ty).clone(),
+    }
+}
+
+/// Checks the parts of a handler's signature that every macro needs
+fn check_signature(function: &ItemFn, kind: &str) -> syn::Result<()> {
+    if function.sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(
+            function.sig.fn_token,
+            format!("{} need to be `async fn`", kind),
+        ));
+    }
+
+    if !function.sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &function.sig.generics,
+            format!("{} can't be generic", kind),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Lets a fieldless enum be used as a slash command argument.
+/// Variants are matched case-insensitively by their kebab-case name, so `InChannel` is `in-channel`.
+#[proc_macro_derive(Argument)]
+pub fn derive_argument(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as DeriveInput);
+    let ident = &input.ident;
+
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return syn::Error::new_spanned(ident, "`Argument` can only be derived for enums")
+                .to_compile_error()
+                .into()
+        }
+    };
+
+    let mut idents = Vec::new();
+    let mut names = Vec::new();
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return syn::Error::new_spanned(
+                variant,
+                "`Argument` can only be derived for enums without fields",
+            )
+            .to_compile_error()
+            .into();
+        }
+
+        idents.push(&variant.ident);
+        names.push(kebab_case(&variant.ident.to_string()));
+    }
+
+    let hint = names.join("|");
+
+    let gen = quote! {
+        impl ::crow::__private::Argument for #ident {
+            fn parse(
+                args: &mut ::crow::__private::Arguments<'_>,
+            ) -> ::std::result::Result<Self, ::crow::__private::ArgumentError> {
+                let token = args
+                    .next_token()
+                    .ok_or(::crow::__private::ArgumentError::Missing)?;
+
+                match token.to_lowercase().replace('_', "-").as_str() {
+                    #(#names => ::std::result::Result::Ok(Self::#idents),)*
+                    _ => ::std::result::Result::Err(::crow::__private::ArgumentError::invalid(token, #hint)),
+                }
+            }
+
+            fn hint() -> ::std::string::String {
+                ::std::string::String::from(#hint)
+            }
+        }
+    };
+
+    gen.into()
+}
+
+fn kebab_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i != 0 {
+            out.push('-');
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+/// Joins the lines of a doc comment into one line
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) => match &meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(doc), ..
+                }) => Some(doc.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join(" "))
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use super::{ApiMethod, Tier};
+use crate::client::User;
+
+/// `auth.test`, which says who the token belongs to
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Test {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TestResponse {
+    pub url: String,
+    pub team: String,
+    pub user: String,
+    pub team_id: String,
+    pub user_id: User,
+    /// Only there for bot tokens
+    pub bot_id: Option<String>,
+}
+
+impl ApiMethod for Test {
+    const NAME: &'static str = "auth.test";
+    const TIER: Tier = Tier::Four;
+    type Response = TestResponse;
+}
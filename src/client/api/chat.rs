@@ -0,0 +1,310 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use super::{ApiMethod, CursorMethod, EmptyResponse, ResponseMetadata, SlackError, Tier};
+use crate::client::{model::message::ScheduledMessage, Channel, Message, User};
+
+/// `chat.postMessage`, which posts in the channel of the message
+#[derive(Debug, Clone, Serialize)]
+pub struct PostMessage {
+    #[serde(flatten)]
+    pub message: Message,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PostMessageResponse {
+    pub channel: Channel,
+    pub ts: String,
+    pub message: Option<PostedMessageInfo>,
+}
+
+/// The message as Slack posted it
+#[derive(Debug, Clone, Deserialize)]
+pub struct PostedMessageInfo {
+    pub text: Option<String>,
+    /// Set if the message is a reply in a thread
+    pub thread_ts: Option<String>,
+}
+
+impl ApiMethod for PostMessage {
+    const NAME: &'static str = "chat.postMessage";
+    const TIER: Tier = Tier::PerChannel;
+    type Response = PostMessageResponse;
+
+    fn channel(&self) -> Option<&Channel> {
+        self.message.channel.as_ref()
+    }
+
+    fn validate(&self) -> Result<(), SlackError> {
+        self.message.validate()
+    }
+}
+
+/// `chat.postEphemeral`, which posts a message only one user in the channel can see
+#[derive(Debug, Clone, Serialize)]
+pub struct PostEphemeral {
+    #[serde(flatten)]
+    pub message: Message,
+    pub user: User,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PostEphemeralResponse {
+    pub message_ts: String,
+}
+
+impl ApiMethod for PostEphemeral {
+    const NAME: &'static str = "chat.postEphemeral";
+    const TIER: Tier = Tier::Four;
+    type Response = PostEphemeralResponse;
+
+    fn validate(&self) -> Result<(), SlackError> {
+        self.message.validate()
+    }
+}
+
+/// `chat.update`, which replaces the message with this `ts` in the channel of the message
+#[derive(Debug, Clone, Serialize)]
+pub struct Update {
+    pub ts: String,
+    #[serde(flatten)]
+    pub message: Message,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateResponse {
+    pub channel: Channel,
+    pub ts: String,
+    pub text: Option<String>,
+}
+
+impl ApiMethod for Update {
+    const NAME: &'static str = "chat.update";
+    type Response = UpdateResponse;
+
+    fn validate(&self) -> Result<(), SlackError> {
+        self.message.validate()
+    }
+}
+
+/// `chat.delete`
+#[derive(Debug, Clone, Serialize)]
+pub struct Delete {
+    pub channel: Channel,
+    pub ts: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeleteResponse {
+    pub channel: Channel,
+    pub ts: String,
+}
+
+impl ApiMethod for Delete {
+    const NAME: &'static str = "chat.delete";
+    type Response = DeleteResponse;
+}
+
+/// `chat.getPermalink`
+#[derive(Debug, Clone, Serialize)]
+pub struct GetPermalink {
+    pub channel: Channel,
+    pub message_ts: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GetPermalinkResponse {
+    pub channel: Channel,
+    pub permalink: String,
+}
+
+impl ApiMethod for GetPermalink {
+    const NAME: &'static str = "chat.getPermalink";
+    const FORM: bool = true;
+    const TIER: Tier = Tier::Four;
+    type Response = GetPermalinkResponse;
+}
+
+/// How far ahead a message can be scheduled
+const MAX_SCHEDULE_AHEAD: i64 = 120 * 24 * 60 * 60;
+
+/// `chat.scheduleMessage`, which has Slack post the message later in its channel
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleMessage {
+    #[serde(flatten)]
+    pub message: Message,
+    /// When to post it, as a UNIX timestamp up to 120 days ahead
+    pub post_at: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScheduleMessageResponse {
+    pub channel: Channel,
+    pub scheduled_message_id: String,
+    pub post_at: i64,
+}
+
+impl ApiMethod for ScheduleMessage {
+    const NAME: &'static str = "chat.scheduleMessage";
+    type Response = ScheduleMessageResponse;
+
+    fn validate(&self) -> Result<(), SlackError> {
+        let now = Utc::now().timestamp();
+        if self.post_at <= now {
+            return Err(SlackError::InvalidInput(
+                "messages can only be scheduled in the future".to_string(),
+            ));
+        }
+        if self.post_at - now > MAX_SCHEDULE_AHEAD {
+            return Err(SlackError::InvalidInput(
+                "messages can only be scheduled up to 120 days ahead".to_string(),
+            ));
+        }
+        self.message.validate()
+    }
+}
+
+/// `chat.deleteScheduledMessage`, which works until shortly before the message is posted
+#[derive(Debug, Clone, Serialize)]
+pub struct DeleteScheduledMessage {
+    pub channel: Channel,
+    pub scheduled_message_id: String,
+}
+
+impl ApiMethod for DeleteScheduledMessage {
+    const NAME: &'static str = "chat.deleteScheduledMessage";
+    type Response = EmptyResponse;
+}
+
+/// `chat.scheduledMessages.list`, which lists the messages the bot has scheduled
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScheduledMessagesList {
+    /// Only the messages scheduled in this channel
+    pub channel: Option<Channel>,
+    /// Only the messages scheduled after this UNIX timestamp
+    pub oldest: Option<i64>,
+    /// Only the messages scheduled before this UNIX timestamp
+    pub latest: Option<i64>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScheduledMessagesListResponse {
+    pub scheduled_messages: Vec<ScheduledMessageInfo>,
+    #[serde(default)]
+    pub response_metadata: ResponseMetadata,
+}
+
+/// A message waiting to be posted
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScheduledMessageInfo {
+    pub id: String,
+    pub channel_id: Channel,
+    pub post_at: i64,
+    pub date_created: i64,
+    #[serde(default)]
+    pub text: String,
+}
+
+impl ScheduledMessageInfo {
+    /// A handle for deleting the message before it's posted
+    pub fn scheduled(&self) -> ScheduledMessage {
+        ScheduledMessage {
+            channel: self.channel_id.clone(),
+            id: self.id.clone(),
+            post_at: self.post_at,
+        }
+    }
+}
+
+impl ApiMethod for ScheduledMessagesList {
+    const NAME: &'static str = "chat.scheduledMessages.list";
+    const FORM: bool = true;
+    type Response = ScheduledMessagesListResponse;
+}
+
+impl CursorMethod for ScheduledMessagesList {
+    type Item = ScheduledMessageInfo;
+
+    fn set_cursor(&mut self, cursor: Option<String>) {
+        self.cursor = cursor;
+    }
+
+    fn set_page_size(&mut self, page_size: u32) {
+        self.limit = Some(page_size);
+    }
+
+    fn into_page(
+        response: ScheduledMessagesListResponse,
+    ) -> (Vec<ScheduledMessageInfo>, Option<String>) {
+        (
+            response.scheduled_messages,
+            response.response_metadata.next_cursor,
+        )
+    }
+}
+
+/// `chat.meMessage`, which posts text in italics like the `/me` command does
+#[derive(Debug, Clone, Serialize)]
+pub struct MeMessage {
+    pub channel: Channel,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MeMessageResponse {
+    pub channel: Channel,
+    pub ts: String,
+}
+
+impl ApiMethod for MeMessage {
+    const NAME: &'static str = "chat.meMessage";
+    type Response = MeMessageResponse;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 24 * 60 * 60;
+
+    fn schedule(post_at: i64) -> Result<(), SlackError> {
+        ScheduleMessage {
+            message: Message::from("caw"),
+            post_at,
+        }
+        .validate()
+    }
+
+    #[test]
+    fn scheduled_messages_have_to_be_in_the_future() {
+        let now = Utc::now().timestamp();
+        assert!(matches!(
+            schedule(now - 60),
+            Err(SlackError::InvalidInput(_))
+        ));
+        assert!(matches!(schedule(now), Err(SlackError::InvalidInput(_))));
+        assert!(schedule(now + 60).is_ok());
+    }
+
+    #[test]
+    fn scheduled_messages_are_at_most_120_days_ahead() {
+        let now = Utc::now().timestamp();
+        assert!(schedule(now + 120 * DAY).is_ok());
+        assert!(matches!(
+            schedule(now + 121 * DAY),
+            Err(SlackError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn scheduled_messages_are_validated() {
+        let empty = ScheduleMessage {
+            message: Message::default(),
+            post_at: Utc::now().timestamp() + DAY,
+        };
+        assert!(matches!(empty.validate(), Err(SlackError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,333 @@
+use serde::{Deserialize, Serialize};
+
+use super::{ApiMethod, CursorMethod, EmptyResponse, ResponseMetadata, Tier};
+use crate::client::{
+    model::{channel::ChannelInfo, message::HistoryMessage},
+    Channel, User,
+};
+
+/// The response of the methods that change a conversation, with how it looks now
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChannelResponse {
+    pub channel: ChannelInfo,
+}
+
+/// `conversations.info`
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Info {
+    pub channel: Channel,
+    /// Also return `num_members`
+    pub include_num_members: Option<bool>,
+}
+
+impl ApiMethod for Info {
+    const NAME: &'static str = "conversations.info";
+    const FORM: bool = true;
+    const TIER: Tier = Tier::Four;
+    type Response = ChannelResponse;
+}
+
+/// `conversations.history`, which lists the messages in a conversation, newest first.
+/// Replies in threads aren't included, except for those also sent to the channel.
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct History {
+    pub channel: Channel,
+    /// Only messages after this `ts`
+    pub oldest: Option<String>,
+    /// Only messages before this `ts`
+    pub latest: Option<String>,
+    /// Include the messages at `oldest` and `latest` too
+    pub inclusive: Option<bool>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HistoryResponse {
+    pub messages: Vec<HistoryMessage>,
+    #[serde(default)]
+    pub has_more: bool,
+    #[serde(default)]
+    pub response_metadata: ResponseMetadata,
+}
+
+impl ApiMethod for History {
+    const NAME: &'static str = "conversations.history";
+    const FORM: bool = true;
+    type Response = HistoryResponse;
+}
+
+impl CursorMethod for History {
+    type Item = HistoryMessage;
+
+    fn set_cursor(&mut self, cursor: Option<String>) {
+        self.cursor = cursor;
+    }
+
+    fn set_page_size(&mut self, page_size: u32) {
+        self.limit = Some(page_size);
+    }
+
+    fn into_page(response: HistoryResponse) -> (Vec<HistoryMessage>, Option<String>) {
+        (response.messages, response.response_metadata.next_cursor)
+    }
+}
+
+/// `conversations.replies`, which lists a thread, oldest first, starting with the message
+/// it's on
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Replies {
+    pub channel: Channel,
+    /// The `ts` of the message the thread is on
+    pub ts: String,
+    pub oldest: Option<String>,
+    pub latest: Option<String>,
+    pub inclusive: Option<bool>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+impl ApiMethod for Replies {
+    const NAME: &'static str = "conversations.replies";
+    const FORM: bool = true;
+    type Response = HistoryResponse;
+}
+
+impl CursorMethod for Replies {
+    type Item = HistoryMessage;
+
+    fn set_cursor(&mut self, cursor: Option<String>) {
+        self.cursor = cursor;
+    }
+
+    fn set_page_size(&mut self, page_size: u32) {
+        self.limit = Some(page_size);
+    }
+
+    fn into_page(response: HistoryResponse) -> (Vec<HistoryMessage>, Option<String>) {
+        (response.messages, response.response_metadata.next_cursor)
+    }
+}
+
+/// `conversations.join`, which adds the bot to a public channel
+#[derive(Debug, Clone, Serialize)]
+pub struct Join {
+    pub channel: Channel,
+}
+
+impl ApiMethod for Join {
+    const NAME: &'static str = "conversations.join";
+    type Response = ChannelResponse;
+}
+
+/// `conversations.leave`
+#[derive(Debug, Clone, Serialize)]
+pub struct Leave {
+    pub channel: Channel,
+}
+
+impl ApiMethod for Leave {
+    const NAME: &'static str = "conversations.leave";
+    type Response = EmptyResponse;
+}
+
+/// `conversations.invite`
+#[derive(Debug, Clone, Serialize)]
+pub struct Invite {
+    pub channel: Channel,
+    /// A comma separated list of up to 1000 user IDs
+    pub users: String,
+}
+
+impl ApiMethod for Invite {
+    const NAME: &'static str = "conversations.invite";
+    type Response = ChannelResponse;
+}
+
+/// `conversations.kick`
+#[derive(Debug, Clone, Serialize)]
+pub struct Kick {
+    pub channel: Channel,
+    pub user: User,
+}
+
+impl ApiMethod for Kick {
+    const NAME: &'static str = "conversations.kick";
+    type Response = EmptyResponse;
+}
+
+/// `conversations.setTopic`
+#[derive(Debug, Clone, Serialize)]
+pub struct SetTopic {
+    pub channel: Channel,
+    pub topic: String,
+}
+
+impl ApiMethod for SetTopic {
+    const NAME: &'static str = "conversations.setTopic";
+    const TIER: Tier = Tier::Two;
+    type Response = ChannelResponse;
+}
+
+/// `conversations.setPurpose`
+#[derive(Debug, Clone, Serialize)]
+pub struct SetPurpose {
+    pub channel: Channel,
+    pub purpose: String,
+}
+
+impl ApiMethod for SetPurpose {
+    const NAME: &'static str = "conversations.setPurpose";
+    const TIER: Tier = Tier::Two;
+    type Response = ChannelResponse;
+}
+
+/// `conversations.rename`
+#[derive(Debug, Clone, Serialize)]
+pub struct Rename {
+    pub channel: Channel,
+    pub name: String,
+}
+
+impl ApiMethod for Rename {
+    const NAME: &'static str = "conversations.rename";
+    const TIER: Tier = Tier::Two;
+    type Response = ChannelResponse;
+}
+
+/// `conversations.archive`
+#[derive(Debug, Clone, Serialize)]
+pub struct Archive {
+    pub channel: Channel,
+}
+
+impl ApiMethod for Archive {
+    const NAME: &'static str = "conversations.archive";
+    const TIER: Tier = Tier::Two;
+    type Response = EmptyResponse;
+}
+
+/// `conversations.unarchive`
+#[derive(Debug, Clone, Serialize)]
+pub struct Unarchive {
+    pub channel: Channel,
+}
+
+impl ApiMethod for Unarchive {
+    const NAME: &'static str = "conversations.unarchive";
+    const TIER: Tier = Tier::Two;
+    type Response = EmptyResponse;
+}
+
+/// `conversations.open`, which opens a direct message, or a group one with several users
+#[derive(Debug, Clone, Serialize)]
+pub struct Open {
+    /// A comma separated list of up to 8 user IDs
+    pub users: String,
+}
+
+impl ApiMethod for Open {
+    const NAME: &'static str = "conversations.open";
+    type Response = ChannelResponse;
+}
+
+/// `conversations.create`
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Create {
+    /// Lowercase letters, numbers, hyphens and underscores, up to 80 characters
+    pub name: String,
+    pub is_private: Option<bool>,
+    pub team_id: Option<String>,
+}
+
+impl ApiMethod for Create {
+    const NAME: &'static str = "conversations.create";
+    const TIER: Tier = Tier::Two;
+    type Response = ChannelResponse;
+}
+
+/// `conversations.list`, which lists the conversations in the workspace
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct List {
+    /// A comma separated list of `public_channel`, `private_channel`, `mpim` and `im`.
+    /// Only public channels are listed if it's not set.
+    pub types: Option<String>,
+    pub exclude_archived: Option<bool>,
+    pub team_id: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListResponse {
+    pub channels: Vec<ChannelInfo>,
+    #[serde(default)]
+    pub response_metadata: ResponseMetadata,
+}
+
+impl ApiMethod for List {
+    const NAME: &'static str = "conversations.list";
+    const FORM: bool = true;
+    const TIER: Tier = Tier::Two;
+    type Response = ListResponse;
+}
+
+impl CursorMethod for List {
+    type Item = ChannelInfo;
+
+    fn set_cursor(&mut self, cursor: Option<String>) {
+        self.cursor = cursor;
+    }
+
+    fn set_page_size(&mut self, page_size: u32) {
+        self.limit = Some(page_size);
+    }
+
+    fn into_page(response: ListResponse) -> (Vec<ChannelInfo>, Option<String>) {
+        (response.channels, response.response_metadata.next_cursor)
+    }
+}
+
+/// `conversations.members`, which lists the members of a conversation
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Members {
+    pub channel: Channel,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MembersResponse {
+    pub members: Vec<User>,
+    #[serde(default)]
+    pub response_metadata: ResponseMetadata,
+}
+
+impl ApiMethod for Members {
+    const NAME: &'static str = "conversations.members";
+    const FORM: bool = true;
+    const TIER: Tier = Tier::Four;
+    type Response = MembersResponse;
+}
+
+impl CursorMethod for Members {
+    type Item = User;
+
+    fn set_cursor(&mut self, cursor: Option<String>) {
+        self.cursor = cursor;
+    }
+
+    fn set_page_size(&mut self, page_size: u32) {
+        self.limit = Some(page_size);
+    }
+
+    fn into_page(response: MembersResponse) -> (Vec<User>, Option<String>) {
+        (response.members, response.response_metadata.next_cursor)
+    }
+}
@@ -0,0 +1,146 @@
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+/// Everything that can go wrong when talking to Slack
+#[derive(Debug)]
+pub enum SlackError {
+    /// What was going to be sent breaks one of Slack's rules, so it wasn't sent
+    InvalidInput(String),
+    /// The request couldn't be sent, or the response couldn't be read
+    Transport(reqwest::Error),
+    /// Slack answered with an HTTP error status
+    Http { status: StatusCode, body: String },
+    /// The response wasn't what the method is documented to return
+    Decode {
+        method: &'static str,
+        error: serde_json::Error,
+    },
+    /// Slack answered with `ok: false`
+    Api(ApiError),
+    /// Slack kept saying to slow down after every retry
+    RateLimited {
+        method: &'static str,
+        retry_after: Duration,
+    },
+}
+
+impl SlackError {
+    /// The error code Slack returned, like `channel_not_found`
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            SlackError::Api(err) => Some(&err.error),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for SlackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SlackError::InvalidInput(err) => write!(f, "invalid input: {}", err),
+            SlackError::Transport(err) => write!(f, "couldn't reach slack: {}", err),
+            SlackError::Http { status, body } => {
+                write!(f, "slack responded with {}: {}", status, body)
+            }
+            SlackError::Decode { method, error } => {
+                write!(f, "unexpected response to {}: {}", method, error)
+            }
+            SlackError::Api(err) => write!(f, "{}", err),
+            SlackError::RateLimited {
+                method,
+                retry_after,
+            } => write!(
+                f,
+                "{} is rate limited, retry after {}s",
+                method,
+                retry_after.as_secs()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SlackError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SlackError::Transport(err) => Some(err),
+            SlackError::Decode { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ApiError> for SlackError {
+    fn from(err: ApiError) -> Self {
+        SlackError::Api(err)
+    }
+}
+
+/// The error code of a failed Web API call, with the details Slack sends along with it
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    pub method: &'static str,
+    /// The error code, like `not_in_channel`
+    pub error: String,
+    /// The scopes the method needs, if the error is `missing_scope`
+    pub needed: Option<String>,
+    /// The scopes the token has, if the error is `missing_scope`
+    pub provided: Option<String>,
+    /// What was wrong with the arguments, from `response_metadata.messages`
+    pub messages: Vec<String>,
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} failed: {}", self.method, self.error)?;
+        if let Some(needed) = &self.needed {
+            write!(
+                f,
+                " (needed: {}, provided: {})",
+                needed,
+                self.provided.as_deref().unwrap_or_default()
+            )?;
+        }
+        if !self.messages.is_empty() {
+            write!(f, " [{}]", self.messages.join("; "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// The fields every Web API response has
+#[derive(Deserialize)]
+pub(super) struct Envelope {
+    pub(super) ok: bool,
+    error: Option<String>,
+    needed: Option<String>,
+    provided: Option<String>,
+    #[serde(default)]
+    response_metadata: ResponseMetadata,
+}
+
+/// Extra information Slack adds to some responses
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ResponseMetadata {
+    /// Where the next page starts, for paginated methods. Empty or missing on the last page.
+    pub next_cursor: Option<String>,
+    #[serde(default)]
+    pub messages: Vec<String>,
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+impl Envelope {
+    pub(super) fn into_error(self, method: &'static str) -> ApiError {
+        ApiError {
+            method,
+            error: self.error.unwrap_or_default(),
+            needed: self.needed,
+            provided: self.provided,
+            messages: self.response_metadata.messages,
+        }
+    }
+}
@@ -0,0 +1,398 @@
+//! Typed access to Slack's Web API.
+//!
+//! Every method is a struct with the arguments it takes, which knows its name and the shape
+//! of its response through `ApiMethod`. They are sent with the `SlackApi` in the `Context`:
+//!
+//! ```ignore
+//! let response = ctx.api().call(&chat::GetPermalink {
+//!     channel: channel.clone(),
+//!     message_ts: ts.clone(),
+//! }).await?;
+//! ```
+
+use std::{sync::Arc, time::Duration};
+
+use reqwest::{header::RETRY_AFTER, Client, ClientBuilder, Response, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::client::Channel;
+
+pub mod auth;
+pub mod chat;
+pub mod conversations;
+mod error;
+mod pagination;
+pub mod pins;
+mod rate_limit;
+pub mod reactions;
+pub mod users;
+pub mod views;
+
+use error::Envelope;
+pub use error::{ApiError, ResponseMetadata, SlackError};
+pub use pagination::{CursorMethod, Paginate};
+use rate_limit::RateLimiter;
+pub use rate_limit::{RetryPolicy, Tier};
+
+/// Where the Web API lives, unless `SlackClientBuilder::api_base_url` says otherwise
+pub const DEFAULT_BASE_URL: &str = "https://slack.com/api/";
+
+/// A Web API method, described by the arguments it takes
+pub trait ApiMethod: Serialize {
+    /// The name of the method, like `chat.postMessage`
+    const NAME: &'static str;
+    /// Sends the arguments form encoded instead of as JSON, which the methods that only read
+    /// need
+    const FORM: bool = false;
+    /// The rate limit tier of the method
+    const TIER: Tier = Tier::Three;
+
+    type Response: DeserializeOwned + Send;
+
+    /// The channel the call is limited in, for methods in the `PerChannel` tier
+    fn channel(&self) -> Option<&Channel> {
+        None
+    }
+
+    /// Checks the arguments against Slack's limits before anything is sent
+    fn validate(&self) -> Result<(), SlackError> {
+        Ok(())
+    }
+}
+
+/// The response of methods that only say whether they worked
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmptyResponse {}
+
+/// How long to wait after a 429 that didn't say
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// Sends Web API calls with the bot token, keeping them under Slack's rate limits.
+/// This is cheap to clone, the connection pool and rate limits are shared.
+#[derive(Debug, Clone)]
+pub struct SlackApi {
+    client: Client,
+    token: String,
+    base_url: String,
+    limiter: Arc<RateLimiter>,
+    retry_policy: RetryPolicy,
+}
+
+impl SlackApi {
+    pub fn new(token: impl Into<String>) -> Self {
+        let client = ClientBuilder::new()
+            .user_agent(concat!(
+                env!("CARGO_PKG_NAME"),
+                "/",
+                env!("CARGO_PKG_VERSION"),
+            ))
+            .build()
+            .unwrap();
+
+        Self::with_client(client, token)
+    }
+
+    pub fn with_client(client: Client, token: impl Into<String>) -> Self {
+        Self {
+            client,
+            token: token.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            limiter: Arc::new(RateLimiter::default()),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Sends calls somewhere other than `https://slack.com/api/`, like a local mock in tests
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        if !self.base_url.ends_with('/') {
+            self.base_url.push('/');
+        }
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Every result of a paginated method, starting from the first page
+    pub fn paginate<M>(&self, method: M) -> Paginate<M>
+    where
+        M: CursorMethod,
+    {
+        Paginate::new(self.clone(), method)
+    }
+
+    /// The HTTP client the calls go through, for requests that aren't Web API calls
+    pub(crate) fn http(&self) -> &Client {
+        &self.client
+    }
+
+    /// Calls the method, returning its response if Slack says it worked.
+    /// Waits for the rate limit of the method if needed, and retries when Slack says to slow
+    /// down or can't be reached, as set by the `RetryPolicy`.
+    pub async fn call<M>(&self, method: &M) -> Result<M::Response, SlackError>
+    where
+        M: ApiMethod,
+    {
+        method.validate()?;
+
+        let response = self.send(method).await?;
+        let status = response.status();
+        let body = response.text().await.map_err(SlackError::Transport)?;
+
+        if !status.is_success() {
+            return Err(SlackError::Http { status, body });
+        }
+
+        let decode = |error| SlackError::Decode {
+            method: M::NAME,
+            error,
+        };
+
+        let value: Value = serde_json::from_str(&body).map_err(decode)?;
+        let envelope = Envelope::deserialize(&value).map_err(decode)?;
+        if !envelope.ok {
+            return Err(envelope.into_error(M::NAME).into());
+        }
+
+        M::Response::deserialize(value).map_err(decode)
+    }
+
+    async fn send<M>(&self, method: &M) -> Result<Response, SlackError>
+    where
+        M: ApiMethod,
+    {
+        let key = match (M::TIER, method.channel()) {
+            (Tier::PerChannel, Some(channel)) => format!("{}:{}", M::NAME, channel.id),
+            _ => M::NAME.to_string(),
+        };
+
+        let mut retries = 0;
+        loop {
+            self.limiter.acquire(&key, M::TIER).await;
+
+            let request = self
+                .client
+                .post(format!("{}{}", self.base_url, M::NAME))
+                .bearer_auth(&self.token);
+            let request = if M::FORM {
+                request.form(method)
+            } else {
+                request.json(method)
+            };
+
+            let can_retry = retries < self.retry_policy.max_retries;
+            let response = match request.send().await {
+                Ok(response) => response,
+                // a call that timed out may have gone through, and posting a message twice is worse
+                // than not posting it
+                Err(err) if can_retry && err.is_connect() => {
+                    tokio::time::sleep(self.retry_policy.backoff(retries)).await;
+                    retries += 1;
+                    continue;
+                }
+                Err(err) => return Err(SlackError::Transport(err)),
+            };
+
+            if response.status() != StatusCode::TOO_MANY_REQUESTS {
+                return Ok(response);
+            }
+
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_RETRY_AFTER);
+
+            if !can_retry {
+                return Err(SlackError::RateLimited {
+                    method: M::NAME,
+                    retry_after,
+                });
+            }
+
+            // the next acquire waits it out, along with every other call to the method
+            self.limiter.pause(&key, M::TIER, retry_after);
+            retries += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{
+        convert::Infallible,
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+    };
+
+    use http_body_util::{BodyExt, Full};
+    use hyper::{
+        body::{Bytes, Incoming},
+        server::conn::http1,
+        service::service_fn,
+        Request,
+    };
+    use hyper_util::rt::TokioIo;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// A local stand-in for Slack, which keeps the body of every request it got
+    pub(crate) struct MockSlack {
+        pub(crate) addr: SocketAddr,
+        pub(crate) requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl MockSlack {
+        pub(crate) fn requests(&self) -> Vec<String> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    /// Answers every call with a canned response, picked by the first segment of the path
+    pub(crate) async fn mock_slack() -> MockSlack {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let received = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let received = received.clone();
+                let service = service_fn(move |req: Request<Incoming>| {
+                    let received = received.clone();
+                    async move {
+                        let route = req.uri().path().split('/').nth(1).unwrap_or_default();
+                        let route = route.to_string();
+                        let body = req.into_body().collect().await.unwrap().to_bytes();
+                        let body = String::from_utf8(body.to_vec()).unwrap();
+                        received.lock().unwrap().push(body.clone());
+
+                        let (status, body) = respond(&route, &body).await;
+                        Ok::<_, Infallible>(
+                            hyper::Response::builder()
+                                .status(status)
+                                .body(Full::new(Bytes::from(body)))
+                                .unwrap(),
+                        )
+                    }
+                });
+
+                tokio::spawn(http1::Builder::new().serve_connection(TokioIo::new(stream), service));
+            }
+        });
+
+        MockSlack { addr, requests }
+    }
+
+    async fn respond(route: &str, body: &str) -> (u16, &'static str) {
+        match route {
+            // two pages of `conversations.members`
+            "members" if body.contains("cursor=page2") => (
+                200,
+                r#"{"ok":true,"members":["U3"],"response_metadata":{"next_cursor":""}}"#,
+            ),
+            "members" => (
+                200,
+                r#"{"ok":true,"members":["U1","U2"],"response_metadata":{"next_cursor":"page2"}}"#,
+            ),
+            "ok" => (
+                200,
+                r#"{"ok":true,"url":"https://crow.slack.com/","team":"Crow","user":"bot","team_id":"T1","user_id":"U1"}"#,
+            ),
+            "missing_scope" => (
+                200,
+                r#"{"ok":false,"error":"missing_scope","needed":"users:read","provided":"chat:write","response_metadata":{"messages":["[ERROR] missing scope"]}}"#,
+            ),
+            "unavailable" => (503, "upstream unavailable"),
+            "slow" => {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                (200, r#"{"ok":true,"channel":"C1","ts":"1.0"}"#)
+            }
+            _ => (200, "<html>not json</html>"),
+        }
+    }
+
+    pub(crate) fn api(mock: &MockSlack, route: &str) -> SlackApi {
+        SlackApi::new("xoxb-test")
+            .base_url(&format!("http://{}/{}", mock.addr, route))
+            .retry_policy(RetryPolicy::never())
+    }
+
+    #[tokio::test]
+    async fn decodes_successful_responses() {
+        let mock = mock_slack().await;
+        let response = api(&mock, "ok").call(&auth::Test {}).await.unwrap();
+        assert_eq!(response.team_id, "T1");
+        assert_eq!(response.user_id.id, "U1");
+        assert_eq!(response.bot_id, None);
+    }
+
+    #[tokio::test]
+    async fn api_errors_keep_their_details() {
+        let mock = mock_slack().await;
+        let err = api(&mock, "missing_scope")
+            .call(&auth::Test {})
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code(), Some("missing_scope"));
+        match err {
+            SlackError::Api(err) => {
+                assert_eq!(err.method, "auth.test");
+                assert_eq!(err.needed.as_deref(), Some("users:read"));
+                assert_eq!(err.provided.as_deref(), Some("chat:write"));
+                assert_eq!(err.messages, ["[ERROR] missing scope"]);
+            }
+            err => panic!("expected an api error, got {:?}", err),
+        }
+    }
+
+    #[tokio::test]
+    async fn error_statuses_are_http_errors() {
+        let mock = mock_slack().await;
+        match api(&mock, "unavailable").call(&auth::Test {}).await {
+            Err(SlackError::Http { status, body }) => {
+                assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+                assert_eq!(body, "upstream unavailable");
+            }
+            result => panic!("expected an http error, got {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn unexpected_bodies_are_decode_errors() {
+        let mock = mock_slack().await;
+        match api(&mock, "garbage").call(&auth::Test {}).await {
+            Err(SlackError::Decode { method, .. }) => assert_eq!(method, "auth.test"),
+            result => panic!("expected a decode error, got {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn timed_out_calls_are_not_sent_again() {
+        let mock = mock_slack().await;
+        let client = Client::builder()
+            .timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+        let api = SlackApi::with_client(client, "xoxb-test")
+            .base_url(&format!("http://{}/slow", mock.addr));
+
+        let result = api
+            .call(&chat::MeMessage {
+                channel: Channel::new("C1".to_string()),
+                text: "waves".to_string(),
+            })
+            .await;
+
+        assert!(matches!(result, Err(SlackError::Transport(err)) if err.is_timeout()));
+        assert_eq!(mock.requests().len(), 1);
+    }
+}
@@ -0,0 +1,215 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{
+    stream::{self, BoxStream},
+    Stream, StreamExt,
+};
+
+use super::{ApiMethod, SlackApi, SlackError};
+
+/// How many results are asked for at once, unless the page size is set
+const DEFAULT_PAGE_SIZE: u32 = 200;
+
+/// A method that returns its results in pages, with a cursor pointing to the next one
+pub trait CursorMethod: ApiMethod + Send + Sync + Unpin + 'static {
+    type Item: Send + 'static;
+
+    fn set_cursor(&mut self, cursor: Option<String>);
+
+    fn set_page_size(&mut self, page_size: u32);
+
+    /// Splits a page into its results and the cursor of the next page, if there is one
+    fn into_page(response: Self::Response) -> (Vec<Self::Item>, Option<String>);
+}
+
+/// Every result of a paginated method, following the cursors as it goes.
+/// Nothing is fetched until the stream is polled, and pages count towards the rate limit of
+/// the method like any other call.
+///
+/// ```ignore
+/// let mut members = channel.members(&ctx).page_size(500).limit(2000);
+/// while let Some(member) = members.try_next().await? {
+///     // ...
+/// }
+/// ```
+pub struct Paginate<M>
+where
+    M: CursorMethod,
+{
+    api: SlackApi,
+    method: Option<M>,
+    page_size: u32,
+    limit: Option<usize>,
+    stream: Option<BoxStream<'static, Result<M::Item, SlackError>>>,
+}
+
+impl<M> Paginate<M>
+where
+    M: CursorMethod,
+{
+    pub(super) fn new(api: SlackApi, method: M) -> Self {
+        Self {
+            api,
+            method: Some(method),
+            page_size: DEFAULT_PAGE_SIZE,
+            limit: None,
+            stream: None,
+        }
+    }
+
+    /// How many results are asked for in every call. Slack may send fewer.
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Stops after this many results, without fetching more pages than needed
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    fn start(&mut self) -> BoxStream<'static, Result<M::Item, SlackError>> {
+        let Some(mut method) = self.method.take() else {
+            return stream::empty().boxed();
+        };
+        method.set_page_size(match self.limit {
+            Some(limit) => self
+                .page_size
+                .min(limit.max(1).try_into().unwrap_or(u32::MAX)),
+            None => self.page_size,
+        });
+
+        // the state is the method to call next, which is gone once the last page was fetched
+        let pages = stream::unfold(
+            (self.api.clone(), Some(method)),
+            |(api, method)| async move {
+                let mut method = method?;
+                match api.call(&method).await {
+                    Ok(response) => {
+                        let (items, cursor) = M::into_page(response);
+                        let next = cursor.filter(|cursor| !cursor.is_empty()).map(|cursor| {
+                            method.set_cursor(Some(cursor));
+                            method
+                        });
+                        Some((Ok(items), (api, next)))
+                    }
+                    Err(err) => Some((Err(err), (api, None))),
+                }
+            },
+        );
+
+        let items = pages.flat_map(|page| match page {
+            Ok(items) => stream::iter(items).map(Ok).left_stream(),
+            Err(err) => stream::once(async { Err(err) }).right_stream(),
+        });
+
+        // fused, so polling a finished stream says it's finished instead of panicking
+        match self.limit {
+            Some(limit) => items.take(limit).fuse().boxed(),
+            None => items.fuse().boxed(),
+        }
+    }
+}
+
+impl<M> Stream for Paginate<M>
+where
+    M: CursorMethod,
+{
+    type Item = Result<M::Item, SlackError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.stream.is_none() {
+            this.stream = Some(this.start());
+        }
+
+        this.stream.as_mut().unwrap().poll_next_unpin(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::client::{
+        api::{
+            conversations::Members,
+            tests::{api, mock_slack},
+        },
+        Channel, User,
+    };
+
+    fn members() -> Members {
+        Members {
+            channel: Channel::new("C1".to_string()),
+            cursor: None,
+            limit: None,
+        }
+    }
+
+    fn ids(users: Vec<User>) -> Vec<String> {
+        users.into_iter().map(|user| user.id).collect()
+    }
+
+    #[tokio::test]
+    async fn follows_cursors_until_the_last_page() {
+        let mock = mock_slack().await;
+        let users: Vec<User> = api(&mock, "members")
+            .paginate(members())
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(ids(users), ["U1", "U2", "U3"]);
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(!requests[0].contains("cursor"));
+        assert!(requests[0].contains("limit=200"));
+        assert!(requests[1].contains("cursor=page2"));
+    }
+
+    #[tokio::test]
+    async fn limit_stops_without_fetching_more_pages() {
+        let mock = mock_slack().await;
+        let users: Vec<User> = api(&mock, "members")
+            .paginate(members())
+            .limit(2)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(ids(users), ["U1", "U2"]);
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 1);
+        // no point asking for more than the limit
+        assert!(requests[0].contains("limit=2"));
+    }
+
+    #[tokio::test]
+    async fn page_size_is_sent_as_the_limit() {
+        let mock = mock_slack().await;
+        let mut pages = api(&mock, "members").paginate(members()).page_size(50);
+        pages.try_next().await.unwrap();
+
+        assert!(mock.requests()[0].contains("limit=50"));
+    }
+
+    #[tokio::test]
+    async fn errors_end_the_stream() {
+        let mock = mock_slack().await;
+        let mut pages = api(&mock, "garbage").paginate(members());
+
+        assert!(matches!(
+            pages.try_next().await,
+            Err(SlackError::Decode { .. })
+        ));
+        assert!(pages.try_next().await.unwrap().is_none());
+        assert!(pages.try_next().await.unwrap().is_none());
+        assert_eq!(mock.requests().len(), 1);
+    }
+}
@@ -0,0 +1,18 @@
+use serde::Serialize;
+
+use super::{ApiMethod, EmptyResponse, Tier};
+use crate::client::Channel;
+
+/// `pins.add`
+#[derive(Debug, Clone, Serialize)]
+pub struct Add {
+    pub channel: Channel,
+    /// The `ts` of the message
+    pub timestamp: String,
+}
+
+impl ApiMethod for Add {
+    const NAME: &'static str = "pins.add";
+    const TIER: Tier = Tier::Two;
+    type Response = EmptyResponse;
+}
@@ -0,0 +1,247 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use tokio::time::Instant;
+
+/// How often a Web API method can be called, as listed in the docs of each method
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tier {
+    /// 1 call a minute
+    One,
+    /// 20 calls a minute
+    Two,
+    /// 50 calls a minute
+    Three,
+    /// 100 calls a minute
+    Four,
+    /// 1 call a second in each channel, which is how posting messages is limited
+    PerChannel,
+}
+
+impl Tier {
+    /// How many calls can be made at once, and how many more are allowed every second
+    fn limits(self) -> (f64, f64) {
+        match self {
+            Tier::One => (1.0, 1.0 / 60.0),
+            Tier::Two => (20.0, 20.0 / 60.0),
+            Tier::Three => (50.0, 50.0 / 60.0),
+            Tier::Four => (100.0, 100.0 / 60.0),
+            Tier::PerChannel => (1.0, 1.0),
+        }
+    }
+}
+
+/// What happens when a call can't get through
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// How many more times a call is tried after being rate limited or failing to connect
+    pub max_retries: u32,
+    /// How long to wait before the first retry after a network error, doubling every retry
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Gives up on the first error
+    pub fn never() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    pub(super) fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+/// A token bucket for one method, or one channel of a `PerChannel` method
+struct Bucket {
+    tokens: f64,
+    capacity: f64,
+    refill: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(tier: Tier, now: Instant) -> Self {
+        let (capacity, refill) = tier.limits();
+        Self {
+            tokens: capacity,
+            capacity,
+            refill,
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill).min(self.capacity);
+        self.updated = now;
+    }
+
+    /// Whether the bucket has refilled completely, so it can be dropped and made again later
+    fn is_full(&self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens + elapsed * self.refill >= self.capacity
+    }
+
+    /// Takes a token, returning how long to wait until it's actually there.
+    /// Tokens can be taken before they are there, so calls queue up in order.
+    fn take(&mut self, now: Instant) -> Duration {
+        self.refill(now);
+        self.tokens -= 1.0;
+
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.refill)
+        }
+    }
+
+    /// Makes sure the next token isn't there for at least `duration`
+    fn pause(&mut self, now: Instant, duration: Duration) {
+        self.refill(now);
+        self.tokens = self.tokens.min(1.0 - duration.as_secs_f64() * self.refill);
+    }
+}
+
+/// Keeps calls under the limits of their tier, before Slack has to say so
+#[derive(Default)]
+pub(super) struct RateLimiter {
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    /// Waits until a call can be made without going over the limit
+    pub(super) async fn acquire(&self, key: &str, tier: Tier) {
+        let wait = {
+            let now = Instant::now();
+            let mut buckets = self.buckets.lock().unwrap();
+
+            // idle buckets are the same as new ones, so per-channel keys don't pile up
+            buckets.retain(|bucket_key, bucket| bucket_key == key || !bucket.is_full(now));
+
+            buckets
+                .entry(key.to_string())
+                .or_insert_with(|| Bucket::new(tier, now))
+                .take(now)
+        };
+
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Holds back every call to `key` after Slack responded with a `Retry-After`
+    pub(super) fn pause(&self, key: &str, tier: Tier, duration: Duration) {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        buckets
+            .entry(key.to_string())
+            .or_insert_with(|| Bucket::new(tier, now))
+            .pause(now, duration);
+    }
+}
+
+impl std::fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimiter").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// How long `acquire` waited, which is all virtual time
+    async fn acquire(limiter: &RateLimiter, key: &str, tier: Tier) -> Duration {
+        let start = Instant::now();
+        limiter.acquire(key, tier).await;
+        start.elapsed()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn tier_four_allows_a_burst_of_100() {
+        let limiter = RateLimiter::default();
+        for _ in 0..100 {
+            assert_eq!(
+                acquire(&limiter, "users.info", Tier::Four).await,
+                Duration::ZERO
+            );
+        }
+
+        // the next token is 60 / 100 seconds away
+        let waited = acquire(&limiter, "users.info", Tier::Four).await;
+        assert!(waited >= Duration::from_millis(599) && waited <= Duration::from_millis(601));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn buckets_refill_over_time() {
+        let limiter = RateLimiter::default();
+        assert_eq!(
+            acquire(&limiter, "team.info", Tier::One).await,
+            Duration::ZERO
+        );
+
+        tokio::time::advance(Duration::from_secs(30)).await;
+        let waited = acquire(&limiter, "team.info", Tier::One).await;
+        assert!(waited >= Duration::from_secs(29) && waited <= Duration::from_secs(31));
+
+        tokio::time::advance(Duration::from_secs(60)).await;
+        assert_eq!(
+            acquire(&limiter, "team.info", Tier::One).await,
+            Duration::ZERO
+        );
+
+        // other methods have their own bucket
+        assert_eq!(
+            acquire(&limiter, "auth.test", Tier::One).await,
+            Duration::ZERO
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retry_after_pauses_the_method() {
+        let limiter = RateLimiter::default();
+        limiter.pause("conversations.info", Tier::Three, Duration::from_secs(10));
+
+        let waited = acquire(&limiter, "conversations.info", Tier::Three).await;
+        assert!(waited >= Duration::from_secs(10) && waited <= Duration::from_millis(10_001));
+
+        // Slack said the limit was hit, so the burst is gone too and calls come at the refill rate
+        let waited = acquire(&limiter, "conversations.info", Tier::Three).await;
+        assert!(waited >= Duration::from_millis(1199) && waited <= Duration::from_millis(1201));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn idle_buckets_are_dropped() {
+        let limiter = RateLimiter::default();
+        limiter
+            .acquire("chat.postMessage:C1", Tier::PerChannel)
+            .await;
+        limiter
+            .acquire("chat.postMessage:C2", Tier::PerChannel)
+            .await;
+        assert_eq!(limiter.buckets.lock().unwrap().len(), 2);
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        limiter
+            .acquire("chat.postMessage:C3", Tier::PerChannel)
+            .await;
+        let buckets = limiter.buckets.lock().unwrap();
+        assert_eq!(buckets.len(), 1);
+        assert!(buckets.contains_key("chat.postMessage:C3"));
+    }
+}
@@ -0,0 +1,19 @@
+use serde::Serialize;
+
+use super::{ApiMethod, EmptyResponse};
+use crate::client::Channel;
+
+/// `reactions.add`
+#[derive(Debug, Clone, Serialize)]
+pub struct Add {
+    pub channel: Channel,
+    /// The `ts` of the message
+    pub timestamp: String,
+    /// The name of the emoji, without colons
+    pub name: String,
+}
+
+impl ApiMethod for Add {
+    const NAME: &'static str = "reactions.add";
+    type Response = EmptyResponse;
+}
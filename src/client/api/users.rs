@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+use super::{ApiMethod, CursorMethod, ResponseMetadata, Tier};
+use crate::client::{model::user::UserInfo, User};
+
+/// The response of the methods that look up one user
+#[derive(Debug, Clone, Deserialize)]
+pub struct UserResponse {
+    pub user: UserInfo,
+}
+
+/// `users.info`
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Info {
+    pub user: User,
+    pub include_locale: Option<bool>,
+}
+
+impl ApiMethod for Info {
+    const NAME: &'static str = "users.info";
+    const FORM: bool = true;
+    const TIER: Tier = Tier::Four;
+    type Response = UserResponse;
+}
+
+/// `users.lookupByEmail`, which needs the `users:read.email` scope
+#[derive(Debug, Clone, Serialize)]
+pub struct LookupByEmail {
+    pub email: String,
+}
+
+impl ApiMethod for LookupByEmail {
+    const NAME: &'static str = "users.lookupByEmail";
+    const FORM: bool = true;
+    type Response = UserResponse;
+}
+
+/// `users.getPresence`
+#[derive(Debug, Clone, Serialize)]
+pub struct GetPresence {
+    pub user: User,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GetPresenceResponse {
+    pub presence: Presence,
+}
+
+/// Whether a user is around
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Presence {
+    Active,
+    Away,
+}
+
+impl ApiMethod for GetPresence {
+    const NAME: &'static str = "users.getPresence";
+    const FORM: bool = true;
+    type Response = GetPresenceResponse;
+}
+
+/// `users.list`, which lists everyone in the workspace, including deactivated users and bots
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct List {
+    pub team_id: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListResponse {
+    pub members: Vec<UserInfo>,
+    #[serde(default)]
+    pub response_metadata: ResponseMetadata,
+}
+
+impl ApiMethod for List {
+    const NAME: &'static str = "users.list";
+    const FORM: bool = true;
+    const TIER: Tier = Tier::Two;
+    type Response = ListResponse;
+}
+
+impl CursorMethod for List {
+    type Item = UserInfo;
+
+    fn set_cursor(&mut self, cursor: Option<String>) {
+        self.cursor = cursor;
+    }
+
+    fn set_page_size(&mut self, page_size: u32) {
+        self.limit = Some(page_size);
+    }
+
+    fn into_page(response: ListResponse) -> (Vec<UserInfo>, Option<String>) {
+        (response.members, response.response_metadata.next_cursor)
+    }
+}
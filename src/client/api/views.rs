@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+use super::{ApiMethod, SlackError, Tier};
+use crate::client::{
+    model::view::{View, ViewInfo},
+    User,
+};
+
+/// The response of every `views.*` method
+#[derive(Debug, Clone, Deserialize)]
+pub struct ViewsResponse {
+    /// The view as Slack shows it now
+    pub view: ViewInfo,
+}
+
+/// `views.open`, which opens a modal within 3 seconds of an interaction
+#[derive(Debug, Clone, Serialize)]
+pub struct Open {
+    pub trigger_id: String,
+    pub view: View,
+}
+
+impl ApiMethod for Open {
+    const NAME: &'static str = "views.open";
+    const TIER: Tier = Tier::Four;
+    type Response = ViewsResponse;
+
+    fn validate(&self) -> Result<(), SlackError> {
+        self.view.validate()
+    }
+}
+
+/// `views.push`, which puts a view on top of the open modal
+#[derive(Debug, Clone, Serialize)]
+pub struct Push {
+    pub trigger_id: String,
+    pub view: View,
+}
+
+impl ApiMethod for Push {
+    const NAME: &'static str = "views.push";
+    const TIER: Tier = Tier::Four;
+    type Response = ViewsResponse;
+
+    fn validate(&self) -> Result<(), SlackError> {
+        self.view.validate()
+    }
+}
+
+/// `views.update`, which replaces a view that's open
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Update {
+    pub view_id: String,
+    /// The hash of the view being replaced, so the update fails if it changed in the meantime
+    pub hash: Option<String>,
+    pub view: View,
+}
+
+impl ApiMethod for Update {
+    const NAME: &'static str = "views.update";
+    const TIER: Tier = Tier::Four;
+    type Response = ViewsResponse;
+
+    fn validate(&self) -> Result<(), SlackError> {
+        self.view.validate()
+    }
+}
+
+/// `views.publish`, which sets the Home tab of a user
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Publish {
+    pub user_id: User,
+    pub hash: Option<String>,
+    pub view: View,
+}
+
+impl ApiMethod for Publish {
+    const NAME: &'static str = "views.publish";
+    const TIER: Tier = Tier::Four;
+    type Response = ViewsResponse;
+
+    fn validate(&self) -> Result<(), SlackError> {
+        self.view.validate()
+    }
+}
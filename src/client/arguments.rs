@@ -0,0 +1,406 @@
+use std::{fmt::Display, time::Duration};
+
+use super::{interactions::InteractionContext, Channel, Message, User};
+
+/// Splits the text of a slash command into arguments.
+/// Arguments are separated by whitespace, and can be wrapped in double quotes to include spaces.
+#[derive(Debug, Clone)]
+pub struct Arguments<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Arguments<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self { rest: text.trim() }
+    }
+
+    /// Whether all arguments have been consumed
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    /// Takes the next argument, removing surrounding quotes
+    pub fn next_token(&mut self) -> Option<&'a str> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let (token, rest) = match self.rest.strip_prefix('"') {
+            Some(quoted) => match quoted.find('"') {
+                Some(end) => (&quoted[..end], &quoted[end + 1..]),
+                None => (quoted, ""),
+            },
+            None => match self.rest.find(char::is_whitespace) {
+                Some(end) => (&self.rest[..end], &self.rest[end..]),
+                None => (self.rest, ""),
+            },
+        };
+
+        self.rest = rest.trim_start();
+        Some(token)
+    }
+
+    /// Takes everything that hasn't been consumed yet
+    pub fn rest(&mut self) -> &'a str {
+        std::mem::take(&mut self.rest)
+    }
+
+    /// Fails if there are arguments left over
+    pub fn finish(self) -> Result<(), ArgumentError> {
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(ArgumentError::Unexpected(self.rest.to_string()))
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ArgumentError {
+    Missing,
+    Invalid { value: String, expected: String },
+    Unexpected(String),
+}
+
+impl ArgumentError {
+    pub fn invalid(value: &str, expected: impl Into<String>) -> Self {
+        Self::Invalid {
+            value: value.to_string(),
+            expected: expected.into(),
+        }
+    }
+}
+
+impl Display for ArgumentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgumentError::Missing => write!(f, "missing a value"),
+            ArgumentError::Invalid { value, expected } => {
+                write!(f, "`{}` is not a valid {}", value, expected)
+            }
+            ArgumentError::Unexpected(rest) => write!(f, "didn't expect `{}`", rest),
+        }
+    }
+}
+
+impl std::error::Error for ArgumentError {}
+
+/// A value that can be parsed from the text of a slash command.
+/// Used by `#[slash_command]` for every parameter after the context.
+pub trait Argument: Sized {
+    fn parse(args: &mut Arguments<'_>) -> Result<Self, ArgumentError>;
+
+    /// A short description of what the argument looks like, used in usage messages
+    fn hint() -> String;
+
+    /// Whether the argument can be left out
+    fn optional() -> bool {
+        false
+    }
+}
+
+macro_rules! impl_from_str_argument {
+    ($hint:literal, $($ty:ty),*) => {
+        $(
+            impl Argument for $ty {
+                fn parse(args: &mut Arguments<'_>) -> Result<Self, ArgumentError> {
+                    let token = args.next_token().ok_or(ArgumentError::Missing)?;
+                    token
+                        .parse()
+                        .map_err(|_| ArgumentError::invalid(token, $hint))
+                }
+
+                fn hint() -> String {
+                    $hint.to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_str_argument!("number", i8, i16, i32, i64, i128, isize);
+impl_from_str_argument!("number", u8, u16, u32, u64, u128, usize);
+impl_from_str_argument!("number", f32, f64);
+impl_from_str_argument!("true/false", bool);
+
+/// A single word, or several words in quotes
+impl Argument for String {
+    fn parse(args: &mut Arguments<'_>) -> Result<Self, ArgumentError> {
+        args.next_token()
+            .map(str::to_string)
+            .ok_or(ArgumentError::Missing)
+    }
+
+    fn hint() -> String {
+        "text".to_string()
+    }
+}
+
+impl<T> Argument for Option<T>
+where
+    T: Argument,
+{
+    fn parse(args: &mut Arguments<'_>) -> Result<Self, ArgumentError> {
+        if args.is_empty() {
+            return Ok(None);
+        }
+
+        T::parse(args).map(Some)
+    }
+
+    fn hint() -> String {
+        T::hint()
+    }
+
+    fn optional() -> bool {
+        true
+    }
+}
+
+/// Everything left in the command, so it has to be the last parameter.
+/// This can be empty, wrap it in an `Option` to tell the difference.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rest(pub String);
+
+impl Argument for Rest {
+    fn parse(args: &mut Arguments<'_>) -> Result<Self, ArgumentError> {
+        Ok(Rest(args.rest().to_string()))
+    }
+
+    fn hint() -> String {
+        "text...".to_string()
+    }
+}
+
+impl std::ops::Deref for Rest {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for Rest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A duration like `90s`, `10m`, `1h30m`, `2d` or `1w`
+impl Argument for Duration {
+    fn parse(args: &mut Arguments<'_>) -> Result<Self, ArgumentError> {
+        let token = args.next_token().ok_or(ArgumentError::Missing)?;
+        parse_duration(token).ok_or_else(|| ArgumentError::invalid(token, "duration"))
+    }
+
+    fn hint() -> String {
+        "duration".to_string()
+    }
+}
+
+fn parse_duration(text: &str) -> Option<Duration> {
+    let mut total = 0u64;
+    let mut number = String::new();
+
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let value: u64 = number.parse().ok()?;
+        number.clear();
+
+        let unit = match c.to_ascii_lowercase() {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 60 * 60 * 24,
+            'w' => 60 * 60 * 24 * 7,
+            _ => return None,
+        };
+
+        // too big to be a duration, which is as wrong as a typo
+        total = total.checked_add(value.checked_mul(unit)?)?;
+    }
+
+    // a unit is needed, otherwise `5` could mean anything
+    if !number.is_empty() || text.is_empty() {
+        return None;
+    }
+
+    Some(Duration::from_secs(total))
+}
+
+/// A user mention, like `<@U012AB3CD|name>`
+impl Argument for User {
+    fn parse(args: &mut Arguments<'_>) -> Result<Self, ArgumentError> {
+        let token = args.next_token().ok_or(ArgumentError::Missing)?;
+        parse_mention(token, "<@")
+            .map(User::new)
+            .ok_or_else(|| ArgumentError::invalid(token, "@user"))
+    }
+
+    fn hint() -> String {
+        "@user".to_string()
+    }
+}
+
+/// A channel mention, like `<#C012AB3CD|name>`
+impl Argument for Channel {
+    fn parse(args: &mut Arguments<'_>) -> Result<Self, ArgumentError> {
+        let token = args.next_token().ok_or(ArgumentError::Missing)?;
+        parse_mention(token, "<#")
+            .map(Channel::new)
+            .ok_or_else(|| ArgumentError::invalid(token, "#channel"))
+    }
+
+    fn hint() -> String {
+        "#channel".to_string()
+    }
+}
+
+fn parse_mention(token: &str, prefix: &str) -> Option<String> {
+    let inner = token.strip_prefix(prefix)?.strip_suffix('>')?;
+    let id = inner.split('|').next()?;
+
+    if id.is_empty() {
+        return None;
+    }
+
+    Some(id.to_string())
+}
+
+/// Formats a parameter for a usage message, like `<who: @user>` or `[note: text...]`
+pub fn usage_of<T>(name: &str) -> String
+where
+    T: Argument,
+{
+    if T::optional() {
+        format!("[{}: {}]", name, T::hint())
+    } else {
+        format!("<{}: {}>", name, T::hint())
+    }
+}
+
+/// Tells the person who ran a command that its arguments couldn't be parsed
+#[doc(hidden)]
+pub async fn report_usage_error(ctx: &InteractionContext, usage: &str, error: String) {
+    let text = format!(
+        "Sorry, I couldn't understand that: {}\nUsage: `{} {}`",
+        error, ctx.command, usage
+    );
+
+    if let Err(err) = ctx.reply(Message::from(text.as_str())).await {
+        println!("Failed to send usage error: {:?}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_whitespace_and_quotes() {
+        let mut args = Arguments::new(r#"  one "two words"   three "unclosed quote"#);
+        assert_eq!(args.next_token(), Some("one"));
+        assert_eq!(args.next_token(), Some("two words"));
+        assert_eq!(args.next_token(), Some("three"));
+        assert_eq!(args.next_token(), Some("unclosed quote"));
+        assert_eq!(args.next_token(), None);
+        assert!(args.finish().is_ok());
+    }
+
+    #[test]
+    fn empty_quotes_are_an_empty_token() {
+        let mut args = Arguments::new(r#""" after"#);
+        assert_eq!(args.next_token(), Some(""));
+        assert_eq!(args.rest(), "after");
+    }
+
+    #[test]
+    fn optional_arguments_can_be_left_out() {
+        let mut args = Arguments::new("5");
+        assert_eq!(<u32>::parse(&mut args).unwrap(), 5);
+        assert_eq!(<Option<u32>>::parse(&mut args).unwrap(), None);
+
+        let mut args = Arguments::new("5 6");
+        <u32>::parse(&mut args).unwrap();
+        assert_eq!(<Option<u32>>::parse(&mut args).unwrap(), Some(6));
+    }
+
+    #[test]
+    fn rest_takes_everything_left() {
+        let mut args = Arguments::new(r#"<@U1> has "quoted" text"#);
+        assert_eq!(User::parse(&mut args).unwrap().id, "U1");
+        assert_eq!(Rest::parse(&mut args).unwrap().0, r#"has "quoted" text"#);
+        assert!(args.finish().is_ok());
+
+        let mut args = Arguments::new("");
+        assert_eq!(Rest::parse(&mut args).unwrap().0, "");
+    }
+
+    #[test]
+    fn missing_required_argument() {
+        let mut args = Arguments::new("");
+        assert!(matches!(
+            String::parse(&mut args),
+            Err(ArgumentError::Missing)
+        ));
+        assert!(matches!(
+            User::parse(&mut args),
+            Err(ArgumentError::Missing)
+        ));
+    }
+
+    #[test]
+    fn leftover_arguments_are_unexpected() {
+        let mut args = Arguments::new("1 2");
+        <u8>::parse(&mut args).unwrap();
+        assert!(matches!(args.finish(), Err(ArgumentError::Unexpected(rest)) if rest == "2"));
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("90s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("2D"), Some(Duration::from_secs(172800)));
+        assert_eq!(parse_duration("1w"), Some(Duration::from_secs(604800)));
+        assert_eq!(parse_duration("5"), None);
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("5x"), None);
+        assert_eq!(parse_duration("m"), None);
+    }
+
+    #[test]
+    fn duration_overflow_is_invalid() {
+        assert_eq!(parse_duration("99999999999999999w"), None);
+        assert_eq!(parse_duration("18446744073709551615s1s"), None);
+        assert_eq!(parse_duration("99999999999999999999999s"), None);
+
+        let mut args = Arguments::new("99999999999999999w");
+        assert!(matches!(
+            Duration::parse(&mut args),
+            Err(ArgumentError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn mentions() {
+        assert_eq!(
+            parse_mention("<@U012AB3CD|name>", "<@"),
+            Some("U012AB3CD".to_string())
+        );
+        assert_eq!(
+            parse_mention("<@U012AB3CD>", "<@"),
+            Some("U012AB3CD".to_string())
+        );
+        assert_eq!(
+            parse_mention("<#C012AB3CD|general>", "<#"),
+            Some("C012AB3CD".to_string())
+        );
+        assert_eq!(parse_mention("<#C012AB3CD>", "<@"), None);
+        assert_eq!(parse_mention("<@>", "<@"), None);
+        assert_eq!(parse_mention("@someone", "<@"), None);
+    }
+}
@@ -0,0 +1,50 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::Arc,
+};
+
+/// Shared application state, keyed by type.
+/// Anything stored in here is available through `ctx.data::<T>()` from events, commands,
+/// actions and the callback. Wrap values in a `Mutex`/`RwLock` if they need to be mutated.
+#[derive(Default, Clone)]
+pub struct TypeMap {
+    values: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl TypeMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert<T>(&mut self, value: T)
+    where
+        T: Any + Send + Sync,
+    {
+        self.values.insert(TypeId::of::<T>(), Arc::new(value));
+    }
+
+    pub fn get<T>(&self) -> Option<Arc<T>>
+    where
+        T: Any + Send + Sync,
+    {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.clone().downcast::<T>().ok())
+    }
+
+    pub fn contains<T>(&self) -> bool
+    where
+        T: Any + Send + Sync,
+    {
+        self.values.contains_key(&TypeId::of::<T>())
+    }
+}
+
+impl std::fmt::Debug for TypeMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TypeMap")
+            .field("len", &self.values.len())
+            .finish()
+    }
+}
@@ -0,0 +1,245 @@
+use std::{collections::HashMap, sync::Arc};
+
+use futures::{future::BoxFuture, FutureExt};
+
+use super::{
+    events::Events,
+    hears::Hears,
+    interactions::{
+        GlobalShortcut, InteractionContext, MessageAction, MessageShortcut, Resolution,
+        SlashCommand,
+    },
+    middleware::Endpoint,
+    modal::Modal,
+    response::{ErrorHandlerFn, HandlerResult, Responder},
+    suggestions::OptionsProvider,
+    Channel, Context, EventHandler, Message, User,
+};
+
+/// Hands events to the handlers registered on the client.
+/// This sits at the end of the middleware chain.
+pub(super) struct Dispatcher<E>
+where
+    E: EventHandler,
+{
+    pub(super) event_handler: E,
+    pub(super) slash_commands: HashMap<String, Arc<SlashCommand>>,
+    pub(super) message_actions: Vec<MessageAction>,
+    pub(super) hears: Vec<Hears>,
+    pub(super) modals: HashMap<String, Arc<dyn Modal>>,
+    pub(super) options_providers: HashMap<String, OptionsProvider>,
+    pub(super) global_shortcuts: HashMap<String, GlobalShortcut>,
+    pub(super) message_shortcuts: HashMap<String, MessageShortcut>,
+    pub(super) error_handler: ErrorHandlerFn,
+}
+
+impl<E> Dispatcher<E>
+where
+    E: EventHandler,
+{
+    async fn dispatch(&self, ctx: Context, event: Events) {
+        match event {
+            Events::MemberJoinedChannel(event) => {
+                self.event_handler
+                    .member_joined_channel(ctx, event.channel, event.user, event.inviter)
+                    .await
+            }
+            Events::AppHomeOpened(event) => self.event_handler.app_home_opened(ctx, event).await,
+            Events::UserChange(user) => self.event_handler.user_change(ctx, user).await,
+            Events::SlashCommandCalled(event) => {
+                let mut ctx = InteractionContext {
+                    ctx,
+                    trigger_id: event.trigger_id,
+                    user: event.user,
+                    channel: event.channel,
+                    command: event.command.clone(),
+                    response_url: Some(event.response_url),
+                    responder: event.responder,
+                };
+
+                let command = match self.slash_commands.get(&event.command[1..].to_lowercase()) {
+                    Some(command) => command,
+                    None => {
+                        eprintln!("No command found for {}", event.command);
+                        return;
+                    }
+                };
+
+                let (mut path, resolution) = command.resolve(&event.text);
+                // keep the name it was called with, in case it was an alias
+                path[0] = &event.command[1..];
+                ctx.command = format!("/{}", path.join(" "));
+
+                match resolution {
+                    Resolution::Run(execute, text) => {
+                        let result = execute(ctx.clone(), text).await;
+                        self.respond(ctx, result).await;
+                    }
+                    Resolution::Help(command, unknown) => {
+                        let mut help = command.help(&ctx.command);
+                        if let Some(unknown) = unknown {
+                            help = format!("I don't know the subcommand `{}`.\n{}", unknown, help);
+                        }
+
+                        if let Err(err) = ctx.reply(Message::from(help)).await {
+                            eprintln!("Failed to send help for {}: {:?}", ctx.command, err);
+                        }
+                    }
+                }
+            }
+            Events::BlockActions(event) => {
+                let ctx = InteractionContext {
+                    ctx,
+                    trigger_id: event.trigger_id,
+                    // actions in views aren't in a channel, so replies go to a DM
+                    channel: event
+                        .channel
+                        .unwrap_or_else(|| Channel::new(event.user.id.clone())),
+                    user: event.user,
+                    command: String::new(),
+                    response_url: event.response_url,
+                    responder: Responder::default(),
+                };
+
+                for action in event.actions {
+                    match self
+                        .message_actions
+                        .iter()
+                        .find(|handler| handler.matches(&action))
+                    {
+                        Some(handler) => {
+                            let result = (handler.execute)(ctx.clone(), action).await;
+                            self.respond(ctx.clone(), result).await;
+                        }
+                        None => eprintln!("No action handler found for {}", action.action_id),
+                    }
+                }
+            }
+            Events::Message(event) => {
+                for hears in &self.hears {
+                    hears.handle(ctx.clone(), event.clone()).await;
+                }
+
+                self.event_handler.message(ctx, event).await
+            }
+            Events::BlockSuggestion(event) => {
+                let mut ctx = Self::view_context(ctx, event.user, String::new());
+                if let Some(channel) = event.channel {
+                    ctx.channel = channel;
+                }
+
+                match self.options_providers.get(&event.query.action_id) {
+                    Some(provider) => {
+                        let action_id = event.query.action_id.clone();
+                        let mut suggestions = (provider.execute)(ctx, event.query).await;
+                        suggestions.truncate();
+                        if event.responder.send(suggestions).is_err() {
+                            eprintln!("Options for {} took too long", action_id);
+                        }
+                    }
+                    None => eprintln!("No options provider found for {}", event.query.action_id),
+                }
+            }
+            Events::GlobalShortcut(event) => {
+                let ctx = Self::view_context(ctx, event.user, event.trigger_id);
+
+                match self.global_shortcuts.get(&event.callback_id) {
+                    Some(shortcut) => {
+                        let result = (shortcut.execute)(ctx.clone()).await;
+                        self.respond(ctx, result).await;
+                    }
+                    None => eprintln!("No shortcut found for {}", event.callback_id),
+                }
+            }
+            Events::MessageShortcut(event) => {
+                let ctx = InteractionContext {
+                    ctx,
+                    trigger_id: event.trigger_id,
+                    user: event.user,
+                    channel: event.message.channel.clone(),
+                    command: String::new(),
+                    response_url: event.response_url,
+                    responder: Responder::default(),
+                };
+
+                match self.message_shortcuts.get(&event.callback_id) {
+                    Some(shortcut) => {
+                        let result = (shortcut.execute)(ctx.clone(), event.message).await;
+                        self.respond(ctx, result).await;
+                    }
+                    None => eprintln!("No shortcut found for {}", event.callback_id),
+                }
+            }
+            Events::ViewSubmission(event) => {
+                let ctx = Self::view_context(ctx, event.user, event.trigger_id);
+
+                match self.modals.get(&event.view.callback_id) {
+                    Some(modal) => {
+                        let callback_id = event.view.callback_id.clone();
+                        let response = modal.submit(ctx, event.view).await;
+                        if event.responder.send(response).is_err() {
+                            eprintln!(
+                                "Modal {} took too long to answer its submission",
+                                callback_id
+                            );
+                        }
+                    }
+                    None => eprintln!("No modal found for {}", event.view.callback_id),
+                }
+            }
+            Events::ViewClosed(event) => {
+                let ctx = Self::view_context(ctx, event.user, String::new());
+
+                match self.modals.get(&event.view.callback_id) {
+                    Some(modal) => modal.closed(ctx, event.view).await,
+                    None => eprintln!("No modal found for {}", event.view.callback_id),
+                }
+            }
+            Events::Callback => self.event_handler.callback(&ctx).await,
+            Events::Raw(event) => self.event_handler.on_raw_event(ctx, event).await,
+        }
+    }
+}
+
+impl<E> Dispatcher<E>
+where
+    E: EventHandler,
+{
+    /// Views and global shortcuts aren't in a channel and have no `response_url`, so replies to
+    /// them go to a DM
+    fn view_context(ctx: Context, user: User, trigger_id: String) -> InteractionContext {
+        InteractionContext {
+            ctx,
+            trigger_id,
+            channel: Channel::new(user.id.clone()),
+            user,
+            command: String::new(),
+            response_url: None,
+            responder: Responder::default(),
+        }
+    }
+
+    /// Sends what a command or action handler returned back to the user
+    async fn respond(&self, ctx: InteractionContext, result: HandlerResult) {
+        match result {
+            Ok(Some(reply)) => {
+                if let Err(err) = ctx.send_reply(reply).await {
+                    eprintln!("Failed to send response: {:?}", err);
+                }
+            }
+            Ok(None) => {}
+            Err(err) => (self.error_handler)(ctx.clone(), err).await,
+        }
+
+        ctx.finish();
+    }
+}
+
+impl<E> Endpoint for Dispatcher<E>
+where
+    E: EventHandler,
+{
+    fn call(&self, ctx: Context, event: Events) -> BoxFuture<'_, ()> {
+        self.dispatch(ctx, event).boxed()
+    }
+}
@@ -1,13 +1,36 @@
-use tokio::sync::mpsc::Sender;
+use serde::Deserialize;
+use serde_json::Value;
 
-use super::{Channel, User};
+use std::sync::Arc;
+
+use super::{
+    modal::ViewResponse,
+    model::{
+        channel::ChannelType,
+        user::UserInfo,
+        view::{InputValue, ViewInfo},
+    },
+    response::{Responder, ResponseUrl},
+    suggestions::{OptionsQuery, Suggestions},
+    Channel, User,
+};
 
 #[derive(Debug, Clone)]
 pub enum Events {
     MemberJoinedChannel(MemberJoinedChannelEvent),
+    AppHomeOpened(AppHomeOpenedEvent),
+    UserChange(UserInfo),
     SlashCommandCalled(SlashCommandCalledEvent),
-    BlockMessageInteraction(BlockMessageInteractionEvent),
+    BlockActions(BlockActionsEvent),
+    Message(MessageEvent),
+    ViewSubmission(ViewSubmissionEvent),
+    ViewClosed(ViewClosedEvent),
+    BlockSuggestion(BlockSuggestionEvent),
+    GlobalShortcut(GlobalShortcutEvent),
+    MessageShortcut(MessageShortcutEvent),
     Callback,
+    /// An event crow doesn't model yet, with the full envelope Slack sent us
+    Raw(Value),
 }
 
 // TODO: channel & user structs
@@ -18,6 +41,25 @@ pub struct MemberJoinedChannelEvent {
     pub inviter: Option<User>,
 }
 
+/// A user opened one of the tabs of the app
+#[derive(Debug, Clone)]
+pub struct AppHomeOpenedEvent {
+    pub user: User,
+    /// The DM between the user and the app
+    pub channel: Channel,
+    pub tab: AppHomeTab,
+    /// The home view that's currently published for the user, if there is one
+    pub view: Option<ViewInfo>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppHomeTab {
+    Home,
+    Messages,
+    About,
+}
+
 #[derive(Debug, Clone)]
 pub struct SlashCommandCalledEvent {
     pub command: String,
@@ -25,14 +67,146 @@ pub struct SlashCommandCalledEvent {
     pub user: User,
     pub channel: Channel,
     pub trigger_id: String,
+    pub response_url: Arc<ResponseUrl>,
+    pub(crate) responder: Responder,
 }
 
+/// Someone used one or more interactive elements in a message, modal or the App Home
 #[derive(Debug, Clone)]
-pub struct BlockMessageInteractionEvent {
-    pub action: String,
+pub struct BlockActionsEvent {
+    pub actions: Vec<BlockAction>,
     pub trigger_id: String,
     pub user: User,
+    /// The channel the message is in. Not set for actions in views.
+    pub channel: Option<Channel>,
+    pub response_url: Option<Arc<ResponseUrl>>,
+}
+
+/// One element that was used, along with where it is
+#[derive(Debug, Clone)]
+pub struct BlockAction {
+    pub action_id: String,
+    pub block_id: String,
+    pub action_ts: String,
+    /// The type of the element and what was picked or entered in it
+    pub value: InputValue,
+    pub container: Container,
+    /// The view the element is in, if it's in a modal or the App Home
+    pub view: Option<ViewInfo>,
     pub username: String,
     pub display_name: String,
+}
+
+impl BlockAction {
+    /// The value of a button, the text of an input, or the value of the first selected option
+    pub fn value(&self) -> Option<&str> {
+        match &self.value {
+            InputValue::Button { value } => value.as_deref(),
+            value => value.text().or_else(|| {
+                value
+                    .selected_options()
+                    .first()
+                    .map(|option| option.value.as_str())
+            }),
+        }
+    }
+}
+
+/// Where an interactive element is
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Container {
+    Message {
+        message_ts: String,
+        channel_id: String,
+        #[serde(default)]
+        is_ephemeral: bool,
+    },
+    /// A modal or the App Home
+    View { view_id: String },
+    /// A container crow doesn't know about yet
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone)]
+pub struct ViewSubmissionEvent {
+    pub view: ViewInfo,
+    pub user: User,
+    pub trigger_id: String,
+    pub(crate) responder: Responder<ViewResponse>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ViewClosedEvent {
+    pub view: ViewInfo,
+    pub user: User,
+}
+
+/// Slack wants options for an `external_select`
+#[derive(Debug, Clone)]
+pub struct BlockSuggestionEvent {
+    pub query: OptionsQuery,
+    pub user: User,
+    /// The channel the message is in. Not set for selects in views.
+    pub channel: Option<Channel>,
+    pub(crate) responder: Responder<Suggestions>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GlobalShortcutEvent {
+    pub callback_id: String,
+    pub trigger_id: String,
+    pub user: User,
+}
+
+#[derive(Debug, Clone)]
+pub struct MessageShortcutEvent {
+    pub callback_id: String,
+    pub trigger_id: String,
+    pub user: User,
+    pub message: ShortcutMessage,
+    pub response_url: Option<Arc<ResponseUrl>>,
+}
+
+/// The message a message shortcut was used on
+#[derive(Debug, Clone)]
+pub struct ShortcutMessage {
+    pub channel: Channel,
+    /// The author, which is missing for some bot messages
+    pub user: Option<User>,
+    /// Set if the message was sent by a bot
+    pub bot_id: Option<String>,
+    pub text: String,
+    pub ts: String,
+    pub thread_ts: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MessageEvent {
     pub channel: Channel,
+    pub channel_type: ChannelType,
+    /// The author, which is missing for some bot messages
+    pub user: Option<User>,
+    /// Set if the message was sent by a bot
+    pub bot_id: Option<String>,
+    pub text: String,
+    pub ts: String,
+    pub thread_ts: Option<String>,
+    pub subtype: Option<String>,
+}
+
+impl MessageEvent {
+    /// Whether this message is a reply in a thread, rather than a top level message
+    pub fn is_thread_reply(&self) -> bool {
+        self.thread_ts
+            .as_ref()
+            .is_some_and(|thread_ts| *thread_ts != self.ts)
+    }
+
+    /// Whether the message mentions the given user
+    pub fn mentions(&self, user: &User) -> bool {
+        self.text.contains(&format!("<@{}>", user.id))
+            || self.text.contains(&format!("<@{}|", user.id))
+    }
 }
@@ -0,0 +1,337 @@
+use futures::future::BoxFuture;
+use regex::Regex;
+
+use super::{
+    events::MessageEvent,
+    model::{bot::Bot, channel::ChannelType},
+    Channel, Context,
+};
+
+/// Called with the message and everything the pattern captured.
+/// For regex patterns the first capture is the whole match, followed by the capture groups
+/// (empty if a group didn't participate in the match). Text patterns capture the matched text,
+/// and predicates capture nothing.
+pub type HearsFn = fn(Context, MessageEvent, Vec<String>) -> BoxFuture<'static, ()>;
+
+/// What a message has to look like for a listener to fire
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    /// The message contains this text
+    Text(String),
+    Regex(Regex),
+    Predicate(fn(&MessageEvent) -> bool),
+}
+
+impl Pattern {
+    fn captures(&self, message: &MessageEvent) -> Option<Vec<String>> {
+        match self {
+            Pattern::Text(text) => message.text.contains(text).then(|| vec![text.clone()]),
+            Pattern::Regex(regex) => regex.captures(&message.text).map(|captures| {
+                captures
+                    .iter()
+                    .map(|capture| capture.map(|c| c.as_str().to_string()).unwrap_or_default())
+                    .collect()
+            }),
+            Pattern::Predicate(predicate) => predicate(message).then(Vec::new),
+        }
+    }
+}
+
+impl From<&str> for Pattern {
+    fn from(text: &str) -> Self {
+        Pattern::Text(text.to_string())
+    }
+}
+
+impl From<String> for Pattern {
+    fn from(text: String) -> Self {
+        Pattern::Text(text)
+    }
+}
+
+impl From<Regex> for Pattern {
+    fn from(regex: Regex) -> Self {
+        Pattern::Regex(regex)
+    }
+}
+
+impl From<fn(&MessageEvent) -> bool> for Pattern {
+    fn from(predicate: fn(&MessageEvent) -> bool) -> Self {
+        Pattern::Predicate(predicate)
+    }
+}
+
+/// Where in a conversation a message has to be
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThreadFilter {
+    #[default]
+    Any,
+    TopLevel,
+    Thread,
+}
+
+/// A handler for messages matching a pattern.
+///
+/// ```ignore
+/// Hears::new(Regex::new(r"remind me in (\d+) minutes").unwrap(), remind)
+///     .channel_type(ChannelType::Im)
+///     .thread(ThreadFilter::TopLevel)
+/// ```
+#[derive(Debug, Clone)]
+pub struct Hears {
+    pattern: Pattern,
+    handler: HearsFn,
+    channels: Vec<String>,
+    channel_types: Vec<ChannelType>,
+    thread: ThreadFilter,
+    mentioned: Option<bool>,
+    ignore_self: bool,
+}
+
+impl Hears {
+    pub fn new(pattern: impl Into<Pattern>, handler: HearsFn) -> Self {
+        Self {
+            pattern: pattern.into(),
+            handler,
+            channels: Vec::new(),
+            channel_types: Vec::new(),
+            thread: ThreadFilter::Any,
+            mentioned: None,
+            ignore_self: true,
+        }
+    }
+
+    /// Only fire in this channel. Can be called multiple times to allow several channels.
+    pub fn channel(mut self, channel: Channel) -> Self {
+        self.channels.push(channel.id);
+        self
+    }
+
+    /// Only fire in this type of channel. Can be called multiple times to allow several types.
+    pub fn channel_type(mut self, channel_type: ChannelType) -> Self {
+        self.channel_types.push(channel_type);
+        self
+    }
+
+    pub fn thread(mut self, thread: ThreadFilter) -> Self {
+        self.thread = thread;
+        self
+    }
+
+    /// Only fire if the bot was (or wasn't) mentioned in the message
+    pub fn mentioned(mut self, mentioned: bool) -> Self {
+        self.mentioned = Some(mentioned);
+        self
+    }
+
+    /// Also fire on messages sent by the bot itself, which are ignored by default.
+    /// If the identity of the bot couldn't be fetched at startup, messages from every bot are
+    /// ignored instead, so a listener can't end up answering itself forever.
+    pub fn include_self(mut self) -> Self {
+        self.ignore_self = false;
+        self
+    }
+
+    /// Checks the filters and pattern against a message, returning the captures if it matches
+    fn matches(&self, message: &MessageEvent, bot: Option<&Bot>) -> Option<Vec<String>> {
+        if self.ignore_self {
+            let from_self = match bot {
+                Some(bot) => is_from(message, bot),
+                None => message.bot_id.is_some(),
+            };
+            if from_self {
+                return None;
+            }
+        }
+
+        if !self.channels.is_empty() && !self.channels.contains(&message.channel.id) {
+            return None;
+        }
+
+        if !self.channel_types.is_empty() && !self.channel_types.contains(&message.channel_type) {
+            return None;
+        }
+
+        match self.thread {
+            ThreadFilter::Any => {}
+            ThreadFilter::TopLevel if message.is_thread_reply() => return None,
+            ThreadFilter::Thread if !message.is_thread_reply() => return None,
+            _ => {}
+        }
+
+        if let Some(mentioned) = self.mentioned {
+            let was_mentioned = bot.is_some_and(|bot| message.mentions(&bot.user));
+            if mentioned != was_mentioned {
+                return None;
+            }
+        }
+
+        self.pattern.captures(message)
+    }
+
+    /// Runs the handler if the message matches
+    pub(super) async fn handle(&self, ctx: Context, message: MessageEvent) {
+        let captures = match self.matches(&message, ctx.bot()) {
+            Some(captures) => captures,
+            None => return,
+        };
+
+        (self.handler)(ctx, message, captures).await;
+    }
+}
+
+fn is_from(message: &MessageEvent, bot: &Bot) -> bool {
+    message
+        .user
+        .as_ref()
+        .is_some_and(|user| user.id == bot.user.id)
+        || message
+            .bot_id
+            .as_ref()
+            .is_some_and(|bot_id| *bot_id == bot.bot_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+
+    use super::*;
+    use crate::client::User;
+
+    fn ignore(
+        _ctx: Context,
+        _message: MessageEvent,
+        _captures: Vec<String>,
+    ) -> BoxFuture<'static, ()> {
+        async {}.boxed()
+    }
+
+    fn bot() -> Bot {
+        Bot {
+            user: User::new("UBOT".to_string()),
+            bot_id: "B1".to_string(),
+            team_id: "T1".to_string(),
+        }
+    }
+
+    fn message(text: &str) -> MessageEvent {
+        MessageEvent {
+            channel: Channel::new("C1".to_string()),
+            channel_type: ChannelType::Channel,
+            user: Some(User::new("U1".to_string())),
+            bot_id: None,
+            text: text.to_string(),
+            ts: "2.0".to_string(),
+            thread_ts: None,
+            subtype: None,
+        }
+    }
+
+    #[test]
+    fn patterns_capture() {
+        let hears = Hears::new(Regex::new(r"remind me in (\d+)(m)?").unwrap(), ignore);
+        assert_eq!(
+            hears.matches(&message("remind me in 10 please"), None),
+            Some(vec![
+                "remind me in 10".to_string(),
+                "10".to_string(),
+                String::new()
+            ])
+        );
+
+        let hears = Hears::new("hello", ignore);
+        assert_eq!(
+            hears.matches(&message("well hello there"), None),
+            Some(vec!["hello".to_string()])
+        );
+        assert_eq!(hears.matches(&message("goodbye"), None), None);
+    }
+
+    #[test]
+    fn channel_filters() {
+        let hears = Hears::new("hi", ignore).channel(Channel::new("C2".to_string()));
+        assert!(hears.matches(&message("hi"), None).is_none());
+
+        let hears = hears.channel(Channel::new("C1".to_string()));
+        assert!(hears.matches(&message("hi"), None).is_some());
+
+        let hears = Hears::new("hi", ignore).channel_type(ChannelType::Im);
+        assert!(hears.matches(&message("hi"), None).is_none());
+
+        let dm = MessageEvent {
+            channel_type: ChannelType::Im,
+            ..message("hi")
+        };
+        assert!(hears.matches(&dm, None).is_some());
+    }
+
+    #[test]
+    fn thread_filters() {
+        let top_level = message("hi");
+        // the parent of a thread has its own ts as thread_ts
+        let parent = MessageEvent {
+            thread_ts: Some("2.0".to_string()),
+            ..message("hi")
+        };
+        let reply = MessageEvent {
+            thread_ts: Some("1.0".to_string()),
+            ..message("hi")
+        };
+
+        let hears = Hears::new("hi", ignore).thread(ThreadFilter::TopLevel);
+        assert!(hears.matches(&top_level, None).is_some());
+        assert!(hears.matches(&parent, None).is_some());
+        assert!(hears.matches(&reply, None).is_none());
+
+        let hears = Hears::new("hi", ignore).thread(ThreadFilter::Thread);
+        assert!(hears.matches(&top_level, None).is_none());
+        assert!(hears.matches(&reply, None).is_some());
+    }
+
+    #[test]
+    fn mention_filters() {
+        let bot = bot();
+        let mentioned = message("hi <@UBOT>");
+
+        let hears = Hears::new("hi", ignore).mentioned(true);
+        assert!(hears.matches(&mentioned, Some(&bot)).is_some());
+        assert!(hears.matches(&message("hi"), Some(&bot)).is_none());
+
+        let hears = Hears::new("hi", ignore).mentioned(false);
+        assert!(hears.matches(&mentioned, Some(&bot)).is_none());
+        assert!(hears.matches(&message("hi"), Some(&bot)).is_some());
+    }
+
+    #[test]
+    fn own_messages_are_ignored() {
+        let bot = bot();
+        let own = MessageEvent {
+            user: Some(User::new("UBOT".to_string())),
+            bot_id: Some("B1".to_string()),
+            ..message("hi")
+        };
+        let other_bot = MessageEvent {
+            user: None,
+            bot_id: Some("B2".to_string()),
+            ..message("hi")
+        };
+
+        let hears = Hears::new("hi", ignore);
+        assert!(hears.matches(&own, Some(&bot)).is_none());
+        assert!(hears.matches(&other_bot, Some(&bot)).is_some());
+        assert!(hears.include_self().matches(&own, Some(&bot)).is_some());
+    }
+
+    #[test]
+    fn every_bot_is_ignored_without_the_bot_identity() {
+        let other_bot = MessageEvent {
+            user: None,
+            bot_id: Some("B2".to_string()),
+            ..message("hi")
+        };
+
+        let hears = Hears::new("hi", ignore);
+        assert!(hears.matches(&other_bot, None).is_none());
+        assert!(hears.matches(&message("hi"), None).is_some());
+    }
+}
@@ -1,35 +1,578 @@
-use std::{future::Future, ops::Fn, pin::Pin};
+use std::{any::Any, sync::Arc};
 
 use futures::future::BoxFuture;
-use reqwest::Client;
+use regex::Regex;
 
 use super::{
-    model::{channel::Channel, user::User},
-    Context, ContextSupplier,
+    api::views,
+    arguments::Arguments,
+    events::{BlockAction, ShortcutMessage},
+    model::{
+        channel::Channel,
+        user::User,
+        view::{View, ViewInfo},
+    },
+    response::{HandlerResult, Reply, Responder, ResponseType, ResponseUrl},
+    Context, ContextSupplier, Message, SlackError,
 };
 
-pub type CommandFn = fn(InteractionContext<'_>, String, User, Channel) -> BoxFuture<'_, ()>;
+/// Called with the text that came after the command
+pub type CommandFn = fn(InteractionContext, String) -> BoxFuture<'static, HandlerResult>;
 
-pub struct InteractionContext<'a> {
-    pub(super) ctx: Context<'a>,
+#[derive(Debug, Clone)]
+pub struct InteractionContext {
+    pub(super) ctx: Context,
     pub trigger_id: String,
-    //pub(super)
+    /// The user who triggered the interaction
+    pub user: User,
+    /// The channel the interaction happened in.
+    /// For interactions that don't happen in a channel, like submitting a modal, this is a DM
+    /// with the user.
+    pub channel: Channel,
+    /// The full command that was run, including subcommands, like `/crow remind`.
+    /// Empty if this isn't a slash command.
+    pub command: String,
+    /// Where deferred responses to the interaction are posted
+    pub(super) response_url: Option<Arc<ResponseUrl>>,
+    pub(super) responder: Responder,
+}
+
+impl InteractionContext {
+    /// Gets a value from the shared state added with `SlackClientBuilder::data`
+    pub fn data<T>(&self) -> Option<Arc<T>>
+    where
+        T: Any + Send + Sync,
+    {
+        self.ctx.data::<T>()
+    }
+
+    /// Sends a message only the user who triggered the interaction can see
+    pub async fn reply(&self, msg: Message) -> Result<(), SlackError> {
+        self.send_reply(Reply::ephemeral(msg)).await
+    }
+
+    /// Posts a response to the `response_url` of the interaction.
+    /// Messages are ephemeral by default, use `Reply` to send them to the whole channel or to
+    /// replace or delete the message the interaction came from.
+    ///
+    /// ```ignore
+    /// ctx.respond(Reply::in_channel("Thanks for voting!").replace_original()).await?;
+    /// ```
+    pub async fn respond(&self, reply: impl Into<Reply>) -> Result<(), SlackError> {
+        match &self.response_url {
+            Some(response_url) => response_url.post(self.ctx.api.http(), &reply.into()).await,
+            None => Err(SlackError::InvalidInput(
+                "this interaction doesn't have a response_url".to_string(),
+            )),
+        }
+    }
+
+    /// Opens a modal for the user who triggered the interaction.
+    /// This has to happen within 3 seconds of the interaction.
+    pub async fn open_modal(&self, view: View) -> Result<ViewInfo, SlackError> {
+        let response = self
+            .ctx
+            .api
+            .call(&views::Open {
+                trigger_id: self.trigger_id.clone(),
+                view,
+            })
+            .await?;
+        Ok(response.view)
+    }
+
+    /// Pushes a view on top of the modal the interaction came from
+    pub async fn push_view(&self, view: View) -> Result<ViewInfo, SlackError> {
+        let response = self
+            .ctx
+            .api
+            .call(&views::Push {
+                trigger_id: self.trigger_id.clone(),
+                view,
+            })
+            .await?;
+        Ok(response.view)
+    }
+
+    /// Replaces an open view. Passing the `hash` of the view makes sure it hasn't been changed
+    /// by something else in the meantime.
+    pub async fn update_view(
+        &self,
+        view_id: &str,
+        hash: Option<&str>,
+        view: View,
+    ) -> Result<ViewInfo, SlackError> {
+        let response = self
+            .ctx
+            .api
+            .call(&views::Update {
+                view_id: view_id.to_string(),
+                hash: hash.map(str::to_string),
+                view,
+            })
+            .await?;
+        Ok(response.view)
+    }
+
+    /// The `response_url` of the interaction, if it has one
+    pub fn response_url(&self) -> Option<&ResponseUrl> {
+        self.response_url.as_deref()
+    }
+
+    /// Sends a reply in the quickest way that's still available: as the response to the
+    /// request Slack is waiting on, through the `response_url`, or as a normal message.
+    pub(super) async fn send_reply(&self, reply: Reply) -> Result<(), SlackError> {
+        // the responder can't report errors, so check before handing it over
+        reply.message.validate()?;
+        let reply = match self.responder.send(Some(reply)) {
+            Ok(()) => return Ok(()),
+            Err(reply) => reply.unwrap(),
+        };
+
+        if let Some(response_url) = &self.response_url {
+            if response_url.remaining_uses() > 0 {
+                return response_url.post(self.ctx.api.http(), &reply).await;
+            }
+        }
+
+        let ephemeral = match reply.response_type {
+            // a DM with the user is already private
+            ResponseType::Ephemeral if self.channel.id != self.user.id => Some(self.user.clone()),
+            _ => None,
+        };
+
+        self.channel
+            .post_message(self, reply.message, ephemeral)
+            .await
+            .map(|_| ())
+    }
+
+    /// Lets Slack know the interaction won't get an immediate response
+    pub(super) fn finish(&self) {
+        let _ = self.responder.send(None);
+    }
 }
 
-impl<'a> ContextSupplier<'a> for InteractionContext<'a> {
-    fn get_context(&self) -> &Context<'a> {
+impl ContextSupplier for InteractionContext {
+    fn get_context(&self) -> &Context {
         &self.ctx
     }
 }
 
+/// A slash command, or a subcommand of one.
+///
+/// Commands can be grouped into trees, where the first word of the text picks the subcommand:
+///
+/// ```ignore
+/// SlashCommand::group("crow")
+///     .description("Everything crow can do")
+///     .subcommand(remind())
+///     .subcommand(poll())
+/// ```
+///
+/// `help` and unknown subcommands are answered with a list of the available subcommands.
+#[derive(Debug)]
 pub struct SlashCommand {
     pub command: String,
-    pub execute: CommandFn,
+    /// Other names the command can be called with
+    pub aliases: Vec<String>,
+    /// Shown in the help listing, taken from the doc comment when using `#[slash_command]`
+    pub description: Option<String>,
+    /// The arguments the command takes, like `<who: @user> [note: text...]`
+    pub usage: String,
+    /// What to run when the command itself is called.
+    /// Groups without a handler show their help instead.
+    pub execute: Option<CommandFn>,
+    pub subcommands: Vec<SlashCommand>,
 }
 
-pub type ActionFn = fn(InteractionContext<'_>, User, String, String, Channel) -> BoxFuture<'_, ()>;
+/// What a command's text resolved to after walking through the subcommands
+pub(super) enum Resolution<'a> {
+    Run(CommandFn, String),
+    Help(&'a SlashCommand, Option<String>),
+}
+
+impl SlashCommand {
+    /// A command that only holds subcommands
+    pub fn group(command: &str) -> Self {
+        Self {
+            command: command.to_string(),
+            aliases: Vec::new(),
+            description: None,
+            usage: String::new(),
+            execute: None,
+            subcommands: Vec::new(),
+        }
+    }
+
+    pub fn alias(mut self, alias: &str) -> Self {
+        self.aliases.push(alias.to_string());
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
 
+    pub fn subcommand(mut self, subcommand: SlashCommand) -> Self {
+        self.subcommands.push(subcommand);
+        self
+    }
+
+    pub fn subcommands(mut self, subcommands: Vec<SlashCommand>) -> Self {
+        self.subcommands.extend(subcommands);
+        self
+    }
+
+    /// Whether the command is called this, either by its name or one of its aliases
+    pub fn is_called(&self, name: &str) -> bool {
+        self.command.eq_ignore_ascii_case(name)
+            || self
+                .aliases
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(name))
+    }
+
+    fn find(&self, name: &str) -> Option<&SlashCommand> {
+        self.subcommands
+            .iter()
+            .find(|subcommand| subcommand.is_called(name))
+    }
+
+    /// Walks down the subcommands using the start of the text.
+    /// Returns the names of the commands on the way, and what should happen.
+    pub(super) fn resolve<'a>(&'a self, text: &str) -> (Vec<&'a str>, Resolution<'a>) {
+        let mut path = vec![self.command.as_str()];
+        let mut command = self;
+        let mut args = Arguments::new(text);
+
+        loop {
+            let mut lookahead = args.clone();
+            let token = if command.subcommands.is_empty() {
+                None
+            } else {
+                lookahead.next_token()
+            };
+
+            match token {
+                Some(token) => {
+                    if let Some(subcommand) = command.find(token) {
+                        path.push(&subcommand.command);
+                        command = subcommand;
+                        args = lookahead;
+                        continue;
+                    }
+
+                    if token.eq_ignore_ascii_case("help") {
+                        return (path, Resolution::Help(command, None));
+                    }
+
+                    // a command with both a handler and subcommands gets anything it doesn't know
+                    return match command.execute {
+                        Some(execute) => (path, Resolution::Run(execute, args.rest().to_string())),
+                        None => (path, Resolution::Help(command, Some(token.to_string()))),
+                    };
+                }
+                None => {
+                    return match command.execute {
+                        Some(execute) => (path, Resolution::Run(execute, args.rest().to_string())),
+                        None => (path, Resolution::Help(command, None)),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Lists the subcommands of this command, formatted for Slack
+    pub fn help(&self, path: &str) -> String {
+        let mut help = format!("*{}*", path);
+        if let Some(description) = &self.description {
+            help += &format!(" - {}", description);
+        }
+
+        if self.execute.is_some() {
+            help += &format!("\n`{}`", format!("{} {}", path, self.usage).trim_end());
+        }
+
+        if !self.subcommands.is_empty() {
+            help += "\nAvailable subcommands:";
+            for subcommand in &self.subcommands {
+                let mut usage = format!("{} {}", path, subcommand.command);
+                if !subcommand.subcommands.is_empty() {
+                    usage += " ...";
+                } else if !subcommand.usage.is_empty() {
+                    usage += &format!(" {}", subcommand.usage);
+                }
+
+                help += &format!("\n• `{}`", usage);
+                if let Some(description) = &subcommand.description {
+                    help += &format!(" - {}", description);
+                }
+                if !subcommand.aliases.is_empty() {
+                    help += &format!(" (also `{}`)", subcommand.aliases.join("`, `"));
+                }
+            }
+            help += &format!("\n• `{} help` - Shows this message", path);
+        }
+
+        help
+    }
+}
+
+pub type ActionFn = fn(InteractionContext, BlockAction) -> BoxFuture<'static, HandlerResult>;
+
+/// Which `action_id`s an action handler is for
+#[derive(Debug, Clone)]
+pub enum ActionId {
+    /// Exactly this `action_id`. Actions that came without an `action_id` are matched by their
+    /// `value` instead.
+    Exact(String),
+    /// Every `action_id` starting with this, like `vote:` for `vote:yes` and `vote:no`
+    Prefix(String),
+    Regex(Regex),
+}
+
+impl ActionId {
+    fn matches(&self, action: &BlockAction) -> bool {
+        match self {
+            ActionId::Exact(id) if action.action_id.is_empty() => action.value() == Some(id),
+            ActionId::Exact(id) => action.action_id == *id,
+            ActionId::Prefix(prefix) => action.action_id.starts_with(prefix.as_str()),
+            ActionId::Regex(regex) => regex.is_match(&action.action_id),
+        }
+    }
+}
+
+impl From<&str> for ActionId {
+    fn from(id: &str) -> Self {
+        ActionId::Exact(id.to_string())
+    }
+}
+
+impl From<String> for ActionId {
+    fn from(id: String) -> Self {
+        ActionId::Exact(id)
+    }
+}
+
+impl From<Regex> for ActionId {
+    fn from(regex: Regex) -> Self {
+        ActionId::Regex(regex)
+    }
+}
+
+/// Handles interactive elements in messages, modals and the App Home.
+/// Every action in an interaction goes to the first handler that matches it.
 pub struct MessageAction {
-    pub action: String,
+    pub action: ActionId,
+    /// Only handle the action if it came from this block
+    pub block_id: Option<String>,
     pub execute: ActionFn,
 }
+
+impl MessageAction {
+    pub fn new(action: impl Into<ActionId>, execute: ActionFn) -> Self {
+        Self {
+            action: action.into(),
+            block_id: None,
+            execute,
+        }
+    }
+
+    /// Only handle the action if it came from this block
+    pub fn block_id(mut self, block_id: &str) -> Self {
+        self.block_id = Some(block_id.to_string());
+        self
+    }
+
+    pub(super) fn matches(&self, action: &BlockAction) -> bool {
+        self.action.matches(action)
+            && self
+                .block_id
+                .as_ref()
+                .is_none_or(|expected| *expected == action.block_id)
+    }
+}
+
+pub type GlobalShortcutFn = fn(InteractionContext) -> BoxFuture<'static, HandlerResult>;
+
+/// A shortcut from the shortcuts menu or the search bar, which isn't tied to a channel.
+/// Replies to it are sent as a DM.
+pub struct GlobalShortcut {
+    pub callback_id: String,
+    pub execute: GlobalShortcutFn,
+}
+
+impl GlobalShortcut {
+    pub fn new(callback_id: &str, execute: GlobalShortcutFn) -> Self {
+        Self {
+            callback_id: callback_id.to_string(),
+            execute,
+        }
+    }
+}
+
+pub type MessageShortcutFn =
+    fn(InteractionContext, ShortcutMessage) -> BoxFuture<'static, HandlerResult>;
+
+/// A shortcut from the menu of a message, which is handed the message it was used on
+pub struct MessageShortcut {
+    pub callback_id: String,
+    pub execute: MessageShortcutFn,
+}
+
+impl MessageShortcut {
+    pub fn new(callback_id: &str, execute: MessageShortcutFn) -> Self {
+        Self {
+            callback_id: callback_id.to_string(),
+            execute,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+
+    use super::*;
+    use crate::client::{events::Container, model::view::InputValue};
+
+    fn button(action_id: &str, value: &str) -> BlockAction {
+        BlockAction {
+            action_id: action_id.to_string(),
+            block_id: String::new(),
+            action_ts: String::new(),
+            value: InputValue::Button {
+                value: Some(value.to_string()),
+            },
+            container: Container::Unknown,
+            view: None,
+            username: String::new(),
+            display_name: String::new(),
+        }
+    }
+
+    #[test]
+    fn exact_action_ids_ignore_values() {
+        let id = ActionId::from("announce");
+        assert!(id.matches(&button("announce", "anything")));
+        assert!(!id.matches(&button("vote", "announce")));
+        // only actions without an action_id fall back to their value
+        assert!(id.matches(&button("", "announce")));
+        assert!(!id.matches(&button("", "vote")));
+    }
+
+    fn run(_ctx: InteractionContext, _text: String) -> BoxFuture<'static, HandlerResult> {
+        async { Ok(None) }.boxed()
+    }
+
+    fn command(name: &str) -> SlashCommand {
+        SlashCommand {
+            execute: Some(run),
+            ..SlashCommand::group(name)
+        }
+    }
+
+    fn tree() -> SlashCommand {
+        SlashCommand::group("/crow")
+            .subcommand(command("remind").alias("r"))
+            .subcommand(SlashCommand::group("admin").subcommand(command("ban")))
+    }
+
+    #[test]
+    fn resolves_subcommands_and_aliases() {
+        let tree = tree();
+
+        let (path, resolution) = tree.resolve("R 10m \"feed the crow\"");
+        assert_eq!(path, ["/crow", "remind"]);
+        assert!(matches!(resolution, Resolution::Run(_, text) if text == "10m \"feed the crow\""));
+
+        let (path, resolution) = tree.resolve("admin ban <@U1>");
+        assert_eq!(path, ["/crow", "admin", "ban"]);
+        assert!(matches!(resolution, Resolution::Run(_, text) if text == "<@U1>"));
+    }
+
+    #[test]
+    fn groups_show_help() {
+        let tree = tree();
+
+        let (path, resolution) = tree.resolve("");
+        assert_eq!(path, ["/crow"]);
+        assert!(
+            matches!(resolution, Resolution::Help(command, None) if command.command == "/crow")
+        );
+
+        let (_, resolution) = tree.resolve("admin help");
+        assert!(
+            matches!(resolution, Resolution::Help(command, None) if command.command == "admin")
+        );
+
+        let (_, resolution) = tree.resolve("nope");
+        assert!(matches!(resolution, Resolution::Help(_, Some(unknown)) if unknown == "nope"));
+    }
+
+    #[test]
+    fn commands_with_handlers_get_unknown_words() {
+        let tree = command("/crow").subcommand(command("remind"));
+
+        let (path, resolution) = tree.resolve("hello there");
+        assert_eq!(path, ["/crow"]);
+        assert!(matches!(resolution, Resolution::Run(_, text) if text == "hello there"));
+    }
+
+    fn interaction_context() -> InteractionContext {
+        InteractionContext {
+            ctx: Context {
+                api: crate::client::api::SlackApi::new("xoxb-test"),
+                data: Arc::new(crate::client::data::TypeMap::new()),
+                bot: None,
+                users: Arc::new(crate::client::model::user::UserCache::new(
+                    std::time::Duration::ZERO,
+                )),
+            },
+            trigger_id: String::new(),
+            user: User::new("U1".to_string()),
+            channel: Channel::new("C1".to_string()),
+            command: "/collide".to_string(),
+            response_url: None,
+            responder: Responder::default(),
+        }
+    }
+
+    /// Takes parameters named like everything the generated code declares
+    #[crate::slash_command]
+    async fn collide(
+        __ctx: InteractionContext,
+        future: u32,
+        value: bool,
+        err: String,
+        __arguments: Option<u8>,
+        __usage: crate::client::arguments::Rest,
+    ) {
+        assert_eq!(__ctx.command, "/collide");
+        assert_eq!(future, 7);
+        assert!(value);
+        assert_eq!(err, "an error");
+        assert_eq!(__arguments, Some(3));
+        assert_eq!(__usage.0, "and the rest");
+    }
+
+    #[tokio::test]
+    async fn slash_command_parameters_can_have_any_name() {
+        let command = collide();
+        assert_eq!(
+            command.usage,
+            "<future: number> <value: true/false> <err: text> [__arguments: number] \
+             <__usage: text...>"
+        );
+
+        let execute = command.execute.unwrap();
+        let result = execute(
+            interaction_context(),
+            r#"7 true "an error" 3 and the rest"#.to_string(),
+        )
+        .await;
+        assert!(matches!(result, Ok(None)));
+    }
+}
@@ -1,10 +1,14 @@
-use hyper::body::Bytes;
+use std::sync::Arc;
+
+use http_body_util::Full;
+use hyper::{body::Bytes, Response};
 use serde::Deserialize;
-use tokio::sync::mpsc;
 
+use super::{bad_request, json_response, ACK_DEADLINE};
 use crate::client::{
     events::{Events, SlashCommandCalledEvent},
-    Channel, Context, SlackApiResponse, User, EVENTS,
+    response::{Responder, ResponseUrl},
+    Channel, User, EVENTS,
 };
 
 #[derive(Deserialize, Debug)]
@@ -14,12 +18,15 @@ pub struct CommandPayload {
     pub trigger_id: String,
     pub user_id: String,
     pub channel_id: String,
-    pub team_id: String,
     pub response_url: String,
 }
 
-pub(super) async fn handle_command(content: String) -> Bytes {
-    let command: CommandPayload = serde_html_form::from_str(&content).unwrap();
+pub(super) async fn handle_command(content: String) -> Response<Full<Bytes>> {
+    let command: CommandPayload = match serde_html_form::from_str(&content) {
+        Ok(command) => command,
+        Err(err) => return bad_request(&format!("malformed slash command: {}", err)),
+    };
+    let (responder, mut ack) = Responder::new();
 
     EVENTS
         .get()
@@ -30,11 +37,26 @@ pub(super) async fn handle_command(content: String) -> Bytes {
             trigger_id: command.trigger_id,
             user: User::new(command.user_id),
             channel: Channel::new(command.channel_id),
+            response_url: Arc::new(ResponseUrl::new(command.response_url)),
+            responder: responder.clone(),
         }))
         .await
         .unwrap();
 
     //println!("{:#?}", command);
 
-    Bytes::from("")
+    // if the handler is quick enough, its reply can be the response to this request
+    let reply = match tokio::time::timeout(ACK_DEADLINE, &mut ack).await {
+        Ok(reply) => reply.ok().flatten(),
+        Err(_) => {
+            // anything sent after this goes to the response_url instead
+            responder.close();
+            ack.try_recv().ok().flatten()
+        }
+    };
+
+    match reply {
+        Some(reply) => json_response(&reply),
+        None => Response::new(Full::new(Bytes::from(""))),
+    }
 }
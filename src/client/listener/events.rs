@@ -1,17 +1,27 @@
-use std::{
-    collections::HashMap,
-    sync::{Arc, RwLock, RwLockWriteGuard},
-};
-
 use hyper::body::Bytes;
 use serde::Deserialize;
-use tokio::sync::mpsc::Sender;
+use serde_json::Value;
 
 use crate::client::{
-    events::{Events, MemberJoinedChannelEvent},
+    events::{AppHomeOpenedEvent, AppHomeTab, Events, MemberJoinedChannelEvent, MessageEvent},
+    model::{
+        channel::ChannelType,
+        user::{UserCache, UserInfo},
+        view::ViewInfo,
+    },
     Channel, User, EVENTS,
 };
 
+/// Message subtypes that are still a user (or bot) saying something.
+/// Everything else, like edits and channel joins, is passed on as a raw event.
+const MESSAGE_SUBTYPES: [&str; 5] = [
+    "bot_message",
+    "file_share",
+    "me_message",
+    "thread_broadcast",
+    "reply_broadcast",
+];
+
 // umm how much of this is important?
 #[derive(Deserialize, Debug)]
 pub struct GenericEvent {
@@ -19,7 +29,7 @@ pub struct GenericEvent {
     //pub token: String,
     //pub team_id: String,
     //pub api_app_id: String,
-    pub event: HashMap<String, String>,
+    pub event: Value,
     //pub event_context: String,
     //pub event_id: String,
     //pub event_time: u32,
@@ -29,8 +39,16 @@ pub struct GenericEvent {
     // another thing goes here about enterprises that is null
 }
 
-pub(super) async fn handle_event(content: String) -> Bytes {
-    let event = match serde_json::from_str::<GenericEvent>(&content) {
+pub(super) async fn handle_event(content: String, users: &UserCache) -> Bytes {
+    let envelope = match serde_json::from_str::<Value>(&content) {
+        Ok(envelope) => envelope,
+        Err(err) => {
+            println!("error: {:?}", err);
+            return Bytes::from("Invalid request");
+        }
+    };
+
+    let event = match serde_json::from_value::<GenericEvent>(envelope.clone()) {
         Ok(event) => event,
         Err(err) => {
             println!("error: {:?}", err);
@@ -40,11 +58,9 @@ pub(super) async fn handle_event(content: String) -> Bytes {
 
     println!("event: {:?}", event);
 
-    match event.event.get("type").unwrap().as_str() {
-        "member_joined_channel" => {
-            let ev = match serde_json::from_str::<MemberJoinedChannel>(
-                format!("{:?}", event.event).as_str(),
-            ) {
+    match event.event.get("type").and_then(Value::as_str) {
+        Some("member_joined_channel") => {
+            let ev = match serde_json::from_value::<MemberJoinedChannel>(event.event) {
                 Ok(member_joined_channel) => member_joined_channel,
                 Err(_) => {
                     return Bytes::from("Invalid request");
@@ -59,15 +75,96 @@ pub(super) async fn handle_event(content: String) -> Bytes {
                 .send(Events::MemberJoinedChannel(MemberJoinedChannelEvent {
                     channel: Channel::new(ev.channel),
                     user: User::new(ev.user),
-                    inviter: ev.inviter.map(|inviter| User::new(inviter)),
+                    inviter: ev.inviter.map(User::new),
+                }))
+                .await
+                .unwrap();
+
+            Bytes::from("200 OK")
+        }
+        Some("app_home_opened") => {
+            let ev = match serde_json::from_value::<AppHomeOpened>(event.event) {
+                Ok(app_home_opened) => app_home_opened,
+                Err(_) => {
+                    return Bytes::from("Invalid request");
+                }
+            };
+
+            EVENTS
+                .get()
+                .unwrap()
+                .send(Events::AppHomeOpened(AppHomeOpenedEvent {
+                    user: User::new(ev.user),
+                    channel: Channel::new(ev.channel),
+                    tab: ev.tab,
+                    view: ev.view,
+                }))
+                .await
+                .unwrap();
+
+            Bytes::from("200 OK")
+        }
+        Some("user_change") => {
+            let ev = match serde_json::from_value::<UserChange>(event.event) {
+                Ok(user_change) => user_change,
+                Err(_) => {
+                    return Bytes::from("Invalid request");
+                }
+            };
+
+            users.invalidate(&ev.user.id);
+
+            EVENTS
+                .get()
+                .unwrap()
+                .send(Events::UserChange(ev.user))
+                .await
+                .unwrap();
+
+            Bytes::from("200 OK")
+        }
+        Some("message")
+            if event
+                .event
+                .get("subtype")
+                .and_then(Value::as_str)
+                .is_none_or(|subtype| MESSAGE_SUBTYPES.contains(&subtype)) =>
+        {
+            let ev = match serde_json::from_value::<RawMessageEvent>(event.event) {
+                Ok(message) => message,
+                Err(_) => {
+                    return Bytes::from("Invalid request");
+                }
+            };
+
+            EVENTS
+                .get()
+                .unwrap()
+                .send(Events::Message(MessageEvent {
+                    channel: Channel::new(ev.channel),
+                    channel_type: ev.channel_type,
+                    user: ev.user.map(User::new),
+                    bot_id: ev.bot_id,
+                    text: ev.text.unwrap_or_default(),
+                    ts: ev.ts,
+                    thread_ts: ev.thread_ts,
+                    subtype: ev.subtype,
                 }))
                 .await
                 .unwrap();
 
-            return Bytes::from("200 OK");
+            Bytes::from("200 OK")
         }
+        // anything we don't model yet gets handed over as is, so it can still be handled
         _ => {
-            return Bytes::from("Invalid request");
+            EVENTS
+                .get()
+                .unwrap()
+                .send(Events::Raw(envelope))
+                .await
+                .unwrap();
+
+            Bytes::from("200 OK")
         }
     }
 }
@@ -77,10 +174,31 @@ pub(super) async fn handle_event(content: String) -> Bytes {
 #[derive(Deserialize, Debug)]
 struct MemberJoinedChannel {
     pub user: String,
-    pub event_ts: String,
     pub channel: String,
-    pub channel_type: String,
-    pub team: String,
     pub inviter: Option<String>,
-    pub enterprise: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct AppHomeOpened {
+    pub user: String,
+    pub channel: String,
+    pub tab: AppHomeTab,
+    pub view: Option<ViewInfo>,
+}
+
+#[derive(Deserialize, Debug)]
+struct UserChange {
+    pub user: UserInfo,
+}
+
+#[derive(Deserialize, Debug)]
+struct RawMessageEvent {
+    pub channel: String,
+    pub channel_type: ChannelType,
+    pub user: Option<String>,
+    pub bot_id: Option<String>,
+    pub text: Option<String>,
+    pub ts: String,
+    pub thread_ts: Option<String>,
+    pub subtype: Option<String>,
 }
@@ -1,8 +1,19 @@
-use hyper::body::Bytes;
+use std::sync::Arc;
+
+use http_body_util::Full;
+use hyper::{body::Bytes, Response};
 use serde::Deserialize;
 
+use super::{bad_request, json_response, ACK_DEADLINE};
 use crate::client::{
-    events::{BlockMessageInteractionEvent, Events},
+    events::{
+        BlockAction, BlockActionsEvent, BlockSuggestionEvent, Container, Events,
+        GlobalShortcutEvent, MessageShortcutEvent, ShortcutMessage, ViewClosedEvent,
+        ViewSubmissionEvent,
+    },
+    model::view::{InputValue, ViewInfo},
+    response::{Responder, ResponseUrl},
+    suggestions::OptionsQuery,
     Channel, User, EVENTS,
 };
 
@@ -10,18 +21,27 @@ use crate::client::{
 struct InteractionPayload {
     #[serde(rename = "type")]
     pub ty: String,
-    pub trigger_id: String,
+    pub trigger_id: Option<String>,
+    pub callback_id: Option<String>,
     pub user: RawUser,
-    pub channel: RawChannel,
+    pub channel: Option<RawChannel>,
     pub actions: Option<Vec<RawAction>>,
-    pub container: RawContainer,
-    //pub user: String,
+    pub container: Option<Container>,
+    pub response_url: Option<String>,
+    pub view: Option<ViewInfo>,
+    pub message: Option<RawMessage>,
+    // only sent with block_suggestion
+    pub action_id: Option<String>,
+    pub block_id: Option<String>,
+    pub value: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
 struct RawUser {
     pub id: String,
     pub username: String,
+    // not sent with shortcuts
+    #[serde(default)]
     pub name: String,
     // TODO: team id
 }
@@ -29,31 +49,34 @@ struct RawUser {
 #[derive(Deserialize, Debug)]
 struct RawChannel {
     pub id: String,
-    #[serde(rename = "name")]
-    pub ty: String,
 }
 
 #[derive(Deserialize, Debug)]
 struct RawAction {
     action_id: String,
+    #[serde(default)]
     block_id: String,
-    #[serde(rename = "value")]
-    action: String,
-}
-
-#[derive(Deserialize, Debug)]
-struct RawContainer {
-    #[serde(rename = "type")]
-    ty: String,
+    #[serde(default)]
+    action_ts: String,
+    #[serde(flatten)]
+    value: InputValue,
 }
 
 #[derive(Deserialize, Debug)]
 struct RawMessage {
     ts: String,
+    #[serde(default)]
+    text: String,
+    user: Option<String>,
+    bot_id: Option<String>,
+    thread_ts: Option<String>,
 }
 
-pub(super) async fn handle_interaction(content: String) -> Bytes {
-    let interaction: InteractionPayload = serde_json::from_str(&content).unwrap();
+pub(super) async fn handle_interaction(content: String) -> Response<Full<Bytes>> {
+    let interaction: InteractionPayload = match serde_json::from_str(&content) {
+        Ok(interaction) => interaction,
+        Err(err) => return bad_request(&format!("malformed interaction payload: {}", err)),
+    };
 
     println!();
     println!();
@@ -62,24 +85,167 @@ pub(super) async fn handle_interaction(content: String) -> Bytes {
     println!("{}", content);
     println!("{:#?}", interaction);
 
-    if interaction.container.ty == "message" {
-        EVENTS
-            .get()
-            .unwrap()
-            .send(Events::BlockMessageInteraction(
-                BlockMessageInteractionEvent {
-                    // how this work?
-                    action: interaction.actions.unwrap()[0].action.clone(),
-                    trigger_id: interaction.trigger_id,
+    match interaction.ty.as_str() {
+        "block_actions" => handle_block_actions(interaction).await,
+        "view_submission" => return handle_view_submission(interaction).await,
+        "block_suggestion" => return handle_block_suggestion(interaction).await,
+        "shortcut" => {
+            EVENTS
+                .get()
+                .unwrap()
+                .send(Events::GlobalShortcut(GlobalShortcutEvent {
+                    callback_id: interaction.callback_id.unwrap_or_default(),
+                    trigger_id: interaction.trigger_id.unwrap_or_default(),
                     user: User::new(interaction.user.id),
-                    username: interaction.user.username,
-                    display_name: interaction.user.name,
-                    channel: Channel::new(interaction.channel.id),
-                },
-            ))
-            .await
-            .unwrap();
+                }))
+                .await
+                .unwrap();
+        }
+        "message_action" => {
+            // some surfaces leave these out, and the event can't be built without them
+            let (Some(message), Some(channel)) = (interaction.message, interaction.channel) else {
+                return bad_request("message_action without a message or channel");
+            };
+
+            EVENTS
+                .get()
+                .unwrap()
+                .send(Events::MessageShortcut(MessageShortcutEvent {
+                    callback_id: interaction.callback_id.unwrap_or_default(),
+                    trigger_id: interaction.trigger_id.unwrap_or_default(),
+                    user: User::new(interaction.user.id),
+                    message: ShortcutMessage {
+                        channel: Channel::new(channel.id),
+                        user: message.user.map(User::new),
+                        bot_id: message.bot_id,
+                        text: message.text,
+                        ts: message.ts,
+                        thread_ts: message.thread_ts,
+                    },
+                    response_url: interaction
+                        .response_url
+                        .map(|url| Arc::new(ResponseUrl::new(url))),
+                }))
+                .await
+                .unwrap();
+        }
+        "view_closed" => {
+            let Some(view) = interaction.view else {
+                return bad_request("view_closed without a view");
+            };
+
+            EVENTS
+                .get()
+                .unwrap()
+                .send(Events::ViewClosed(ViewClosedEvent {
+                    view,
+                    user: User::new(interaction.user.id),
+                }))
+                .await
+                .unwrap();
+        }
+        _ => {}
     }
 
-    Bytes::from("")
+    Response::new(Full::new(Bytes::from("")))
+}
+
+async fn handle_block_actions(interaction: InteractionPayload) {
+    let container = interaction.container.unwrap_or(Container::Unknown);
+    let actions = interaction
+        .actions
+        .unwrap_or_default()
+        .into_iter()
+        .map(|action| BlockAction {
+            action_id: action.action_id,
+            block_id: action.block_id,
+            action_ts: action.action_ts,
+            value: action.value,
+            container: container.clone(),
+            view: interaction.view.clone(),
+            username: interaction.user.username.clone(),
+            display_name: interaction.user.name.clone(),
+        })
+        .collect();
+
+    EVENTS
+        .get()
+        .unwrap()
+        .send(Events::BlockActions(BlockActionsEvent {
+            actions,
+            trigger_id: interaction.trigger_id.unwrap_or_default(),
+            user: User::new(interaction.user.id),
+            channel: interaction.channel.map(|channel| Channel::new(channel.id)),
+            response_url: interaction
+                .response_url
+                .map(|url| Arc::new(ResponseUrl::new(url))),
+        }))
+        .await
+        .unwrap();
+}
+
+async fn handle_view_submission(interaction: InteractionPayload) -> Response<Full<Bytes>> {
+    let Some(view) = interaction.view else {
+        return bad_request("view_submission without a view");
+    };
+
+    let (responder, mut ack) = Responder::new();
+
+    EVENTS
+        .get()
+        .unwrap()
+        .send(Events::ViewSubmission(ViewSubmissionEvent {
+            view,
+            user: User::new(interaction.user.id),
+            trigger_id: interaction.trigger_id.unwrap_or_default(),
+            responder: responder.clone(),
+        }))
+        .await
+        .unwrap();
+
+    // the response_action can only be given as the response to this request
+    let response = match tokio::time::timeout(ACK_DEADLINE, &mut ack).await {
+        Ok(response) => response.ok(),
+        Err(_) => {
+            responder.close();
+            ack.try_recv().ok()
+        }
+    };
+
+    match response {
+        Some(response) => json_response(&response),
+        None => Response::new(Full::new(Bytes::from(""))),
+    }
+}
+
+async fn handle_block_suggestion(interaction: InteractionPayload) -> Response<Full<Bytes>> {
+    let (responder, mut ack) = Responder::new();
+
+    EVENTS
+        .get()
+        .unwrap()
+        .send(Events::BlockSuggestion(BlockSuggestionEvent {
+            query: OptionsQuery {
+                action_id: interaction.action_id.unwrap_or_default(),
+                block_id: interaction.block_id.unwrap_or_default(),
+                value: interaction.value.unwrap_or_default(),
+                view: interaction.view,
+            },
+            user: User::new(interaction.user.id),
+            channel: interaction.channel.map(|channel| Channel::new(channel.id)),
+            responder: responder.clone(),
+        }))
+        .await
+        .unwrap();
+
+    // options can only be given as the response to this request
+    let suggestions = match tokio::time::timeout(ACK_DEADLINE, &mut ack).await {
+        Ok(suggestions) => suggestions.ok(),
+        Err(_) => {
+            responder.close();
+            ack.try_recv().ok()
+        }
+    };
+
+    json_response(&suggestions.unwrap_or_default())
 }
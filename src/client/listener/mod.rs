@@ -1,24 +1,29 @@
-use std::{convert::Infallible, future::Future, net::SocketAddr, pin::Pin, sync::Arc};
-use tokio::sync::{mpsc::Sender, Mutex, RwLock};
+use std::{
+    convert::Infallible, future::Future, net::SocketAddr, pin::Pin, sync::Arc, time::Duration,
+};
 
 use http_body_util::Full;
 use hyper::{
     body::{Bytes, Incoming},
     server::conn::http1,
-    service::{service_fn, Service},
-    Request, Response,
+    service::Service,
+    Request, Response, StatusCode,
 };
 use hyper_util::rt::TokioIo;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tokio::net::TcpListener;
 
-use super::events::Events;
+use super::model::user::UserCache;
 
 mod commands;
 mod events;
 mod interactions;
 mod verification;
 
+/// How long we wait for a handler before acknowledging a request without a response.
+/// Slack gives up after 3 seconds, so this leaves some room for the network.
+const ACK_DEADLINE: Duration = Duration::from_millis(2500);
+
 /// A generic slack request
 /// We only have the type as that is the only value we need to see where the data should go.
 #[derive(Deserialize)]
@@ -36,11 +41,14 @@ struct SlackFormRequest {
 async fn handle_req(
     req: Request<hyper::body::Incoming>,
     secret: String,
+    users: Arc<UserCache>,
 ) -> Result<Response<Full<Bytes>>, Infallible> {
     println!("req");
 
     let headers = req.headers().clone();
-    let ty = headers.get("Content-Type").unwrap();
+    let Some(ty) = headers.get("Content-Type") else {
+        return Ok(bad_request("no Content-Type"));
+    };
 
     let content = match verification::verify(req, secret).await {
         Ok(content) => content,
@@ -48,17 +56,19 @@ async fn handle_req(
     };
 
     if ty == "application/json" {
-        return Ok(Response::new(Full::new(handle_json(content).await?)));
+        return Ok(Response::new(Full::new(
+            handle_json(content, &users).await?,
+        )));
     }
 
     if ty == "application/x-www-form-urlencoded" {
-        return Ok(Response::new(Full::new(handle_form(content).await?)));
+        return handle_form(content).await;
     }
 
     Ok(Response::new(Full::new(Bytes::from("Invalid request"))))
 }
 
-async fn handle_json(content: String) -> Result<Bytes, Infallible> {
+async fn handle_json(content: String, users: &UserCache) -> Result<Bytes, Infallible> {
     let ty = match serde_json::from_str::<SlackJsonRequest>(content.as_str()) {
         Ok(SlackJsonRequest { r#type }) => r#type,
         Err(_) => return Ok(Bytes::from("Invalid request")),
@@ -68,27 +78,48 @@ async fn handle_json(content: String) -> Result<Bytes, Infallible> {
 
     match ty.as_str() {
         "url_verification" => Ok(verification::url_verification(content).await),
-        "event_callback" => Ok(events::handle_event(content).await),
+        "event_callback" => Ok(events::handle_event(content, users).await),
         _ => Ok(Bytes::from("Invalid request")),
     }
 }
 
-async fn handle_form(content: String) -> Result<Bytes, Infallible> {
+async fn handle_form(content: String) -> Result<Response<Full<Bytes>>, Infallible> {
     println!("got form request: {}", content);
-    let form: SlackFormRequest = serde_html_form::from_str(&content).unwrap();
+    let form: SlackFormRequest = match serde_html_form::from_str(&content) {
+        Ok(form) => form,
+        Err(err) => return Ok(bad_request(&format!("malformed form: {}", err))),
+    };
 
     if form.command.is_some() {
         return Ok(commands::handle_command(content).await);
     }
 
-    if form.payload.is_some() {
-        return Ok(interactions::handle_interaction(form.payload.unwrap()).await);
+    if let Some(payload) = form.payload {
+        return Ok(interactions::handle_interaction(payload).await);
     }
 
-    Ok(Bytes::from(""))
+    Ok(Response::new(Full::new(Bytes::from(""))))
+}
+
+/// A response with a JSON body, which Slack needs the content type for
+fn json_response(body: &impl Serialize) -> Response<Full<Bytes>> {
+    Response::builder()
+        .header("Content-Type", "application/json")
+        .body(Full::new(Bytes::from(serde_json::to_vec(body).unwrap())))
+        .unwrap()
 }
 
-pub async fn listen(port: u16, signing_secret: String) {
+/// Tells Slack the request didn't make sense, instead of bringing down the connection
+fn bad_request(reason: &str) -> Response<Full<Bytes>> {
+    eprintln!("Rejecting request: {}", reason);
+
+    Response::builder()
+        .status(StatusCode::BAD_REQUEST)
+        .body(Full::new(Bytes::from("Invalid request")))
+        .unwrap()
+}
+
+pub(crate) async fn listen(port: u16, signing_secret: String, users: Arc<UserCache>) {
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
 
     // We create a TcpListener and bind it to 127.0.0.1:3000
@@ -98,6 +129,7 @@ pub async fn listen(port: u16, signing_secret: String) {
 
     let service = RequestHandler {
         secret: signing_secret.clone(),
+        users,
     };
 
     while let Ok((stream, _)) = listener.accept().await {
@@ -123,6 +155,8 @@ pub async fn listen(port: u16, signing_secret: String) {
 #[derive(Clone)]
 struct RequestHandler {
     secret: String,
+    /// Cleared as soon as a user changes, before middleware gets a chance to drop the event
+    users: Arc<UserCache>,
 }
 
 impl Service<Request<Incoming>> for RequestHandler {
@@ -131,6 +165,82 @@ impl Service<Request<Incoming>> for RequestHandler {
     type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
 
     fn call(&self, req: Request<Incoming>) -> Self::Future {
-        Box::pin(handle_req(req, self.secret.clone()))
+        Box::pin(handle_req(req, self.secret.clone(), self.users.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use hex::ToHex;
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    use super::*;
+
+    const SECRET: &str = "8f742231b10e8888abcd99yyyzzz85a5";
+
+    async fn serve() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let service = RequestHandler {
+            secret: SECRET.to_string(),
+            users: Arc::new(UserCache::new(Duration::ZERO)),
+        };
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let svc = service.clone();
+                tokio::spawn(http1::Builder::new().serve_connection(TokioIo::new(stream), svc));
+            }
+        });
+
+        addr
+    }
+
+    /// Sends a request signed the way Slack signs them
+    async fn post(addr: SocketAddr, content_type: Option<&str>, body: &str) -> StatusCode {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            .to_string();
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(format!("v0:{}:{}", timestamp, body).as_bytes());
+        let signature = mac.finalize().into_bytes().encode_hex::<String>();
+
+        let mut request = reqwest::Client::new()
+            .post(format!("http://{}/", addr))
+            .header("X-Slack-Request-Timestamp", timestamp)
+            .header("X-Slack-Signature", format!("v0={}", signature))
+            .body(body.to_string());
+        if let Some(content_type) = content_type {
+            request = request.header("Content-Type", content_type);
+        }
+
+        let status = request.send().await.unwrap().status();
+        StatusCode::from_u16(status.as_u16()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn malformed_requests_are_bad_requests() {
+        let addr = serve().await;
+        let form = Some("application/x-www-form-urlencoded");
+
+        // a slash command without most of its fields
+        assert_eq!(
+            post(addr, form, "command=%2Fping").await,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            post(addr, form, "payload=%7Bnot%20json").await,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            post(addr, form, "command=a&command=b").await,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(post(addr, None, "text=hi").await, StatusCode::BAD_REQUEST);
     }
 }
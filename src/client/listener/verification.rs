@@ -75,5 +75,5 @@ pub(super) async fn url_verification(content: String) -> Bytes {
         Err(_) => return Bytes::from("Invalid request"),
     };
 
-    Bytes::from(format!("{}", challenge))
+    Bytes::from(challenge)
 }
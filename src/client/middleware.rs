@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+
+use super::{events::Events, Context};
+
+/// Wraps the dispatch of every event, command and interaction.
+///
+/// Middleware runs in the order it was registered on the `SlackClientBuilder`. Each one gets the
+/// event before it reaches the handlers and decides what happens with it: it can inspect or mutate
+/// it, stop it by not calling `next`, or do work after `next.run(..)` returns.
+///
+/// ```ignore
+/// struct IgnoreBots;
+///
+/// impl Middleware for IgnoreBots {
+///     fn handle<'a>(&'a self, ctx: Context, event: Events, next: Next<'a>) -> BoxFuture<'a, ()> {
+///         async move {
+///             if !is_bot(&event) {
+///                 next.run(ctx, event).await;
+///             }
+///         }
+///         .boxed()
+///     }
+/// }
+/// ```
+pub trait Middleware: Send + Sync + 'static {
+    fn handle<'a>(&'a self, ctx: Context, event: Events, next: Next<'a>) -> BoxFuture<'a, ()>;
+}
+
+/// The end of the middleware chain, which actually hands the event to its handler
+pub(super) trait Endpoint: Send + Sync {
+    fn call(&self, ctx: Context, event: Events) -> BoxFuture<'_, ()>;
+}
+
+/// The rest of the middleware chain
+pub struct Next<'a> {
+    middleware: &'a [Arc<dyn Middleware>],
+    endpoint: &'a dyn Endpoint,
+}
+
+impl<'a> Next<'a> {
+    pub(super) fn new(middleware: &'a [Arc<dyn Middleware>], endpoint: &'a dyn Endpoint) -> Self {
+        Self {
+            middleware,
+            endpoint,
+        }
+    }
+
+    /// Passes the event on to the next middleware, or to the handler if this is the last one
+    pub async fn run(self, ctx: Context, event: Events) {
+        match self.middleware.split_first() {
+            Some((middleware, rest)) => {
+                middleware
+                    .handle(ctx, event, Next::new(rest, self.endpoint))
+                    .await
+            }
+            None => self.endpoint.call(ctx, event).await,
+        }
+    }
+}
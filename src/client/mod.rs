@@ -1,86 +1,163 @@
 use std::{
-    collections::HashMap, future::Future, net::SocketAddr, pin::Pin, sync::OnceLock, time::Duration,
+    any::Any,
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, OnceLock},
+    time::Duration,
 };
 
-use events::Events;
-use hyper::{server::conn::http1, service::service_fn};
-use hyper_util::rt::TokioIo;
-use interactions::{ActionFn, CommandFn, InteractionContext, MessageAction, SlashCommand};
-use reqwest::{Client, ClientBuilder};
-use serde::Deserialize;
-use tokio::{
-    net::TcpListener,
-    sync::mpsc::{Receiver, Sender},
+use api::{RetryPolicy, SlackApi, DEFAULT_BASE_URL};
+use data::TypeMap;
+use dispatch::Dispatcher;
+use events::{AppHomeOpenedEvent, Events, MessageEvent};
+use hears::Hears;
+use interactions::{GlobalShortcut, MessageAction, MessageShortcut, SlashCommand};
+use middleware::{Middleware, Next};
+use modal::Modal;
+use model::{
+    bot::Bot,
+    user::{UserCache, UserInfo},
+};
+use response::{default_error_handler, ErrorHandlerFn};
+use suggestions::OptionsProvider;
+use tokio::sync::{
+    mpsc::{Receiver, Sender},
+    Semaphore,
 };
 
+pub mod api;
+pub mod arguments;
+pub mod data;
+mod dispatch;
 pub mod events;
+pub mod hears;
 pub mod model;
 #[macro_use]
 pub mod interactions;
 pub mod listener;
+pub mod middleware;
+pub mod modal;
+pub mod response;
+pub mod suggestions;
 
+pub use api::SlackError;
 pub use model::{channel::Channel, message::Message, user::User};
 
 static EVENTS: OnceLock<Sender<Events>> = OnceLock::new();
 
-#[derive(Debug)]
-pub enum SlackError {
-    InvalidInput(String),
-    ApiError(String),
-}
+/// How many handlers can run at the same time if no limit is set on the builder
+const DEFAULT_CONCURRENCY: usize = 64;
 
-#[derive(Deserialize)]
-pub(super) struct SlackApiResponse {
-    ok: bool,
-    error: Option<String>,
-}
+/// How many events can wait to be dispatched before the listener has to wait for room
+const EVENT_QUEUE_CAPACITY: usize = 1024;
+
+/// How long `User::info` results are kept if no other TTL is set on the builder
+const DEFAULT_USER_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Handles events sent by Slack.
+/// Every method has a default implementation that does nothing, so only the events you care about
+/// need to be implemented.
+///
+/// Handlers are shared between concurrently running tasks, so they need to be `Send + Sync`.
+/// Implementations can still use `async fn`, as long as the future they return is `Send`.
+pub trait EventHandler: Send + Sync + 'static {
+    fn member_joined_channel(
+        &self,
+        _ctx: Context,
+        _channel: Channel,
+        _user: User,
+        _inviter: Option<User>,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Called when a user opens one of the app's tabs, which is when their App Home can be
+    /// published with `User::publish_home`
+    fn app_home_opened(
+        &self,
+        _ctx: Context,
+        _event: AppHomeOpenedEvent,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Called when a user changes their profile. Their cached `User::info` is already gone by
+    /// the time this runs.
+    fn user_change(&self, _ctx: Context, _user: UserInfo) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Called for every message posted in a channel the bot is in, whether or not it matched
+    /// any of the `Hears` listeners
+    fn message(&self, _ctx: Context, _message: MessageEvent) -> impl Future<Output = ()> + Send {
+        async {}
+    }
 
-pub trait EventHandler {
-    async fn member_joined_channel(
+    /// Runs every 60 seconds
+    fn callback(&self, _ctx: &Context) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
+    /// Called for any event type crow doesn't model yet, with the full event envelope.
+    fn on_raw_event(
         &self,
-        ctx: Context,
-        channel: Channel,
-        user: User,
-        inviter: Option<User>,
-    );
-    async fn callback(&self, ctx: &Context);
+        _ctx: Context,
+        _event: serde_json::Value,
+    ) -> impl Future<Output = ()> + Send {
+        async {}
+    }
 }
 
 pub struct SlackClient<E>
 where
-    E: EventHandler + Send,
+    E: EventHandler,
 {
     port: u16,
-    event_handler: E,
+    dispatcher: Arc<Dispatcher<E>>,
+    middleware: Arc<Vec<Arc<dyn Middleware>>>,
     event_queue: (Sender<Events>, Receiver<Events>),
-    slash_commands: HashMap<String, CommandFn>,
-    message_actions: HashMap<String, ActionFn>,
     signing_secret: String,
-    token: String,
-    client: Client,
+    api: SlackApi,
+    data: Arc<TypeMap>,
+    concurrency: Arc<Semaphore>,
+    bot: Option<Arc<Bot>>,
+    users: Arc<UserCache>,
 }
 
 impl<E> SlackClient<E>
 where
-    E: EventHandler + Send,
+    E: EventHandler,
 {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new() -> SlackClientBuilder<E> {
-        let client = ClientBuilder::new()
-            .user_agent(concat!(
-                env!("CARGO_PKG_NAME"),
-                "/",
-                env!("CARGO_PKG_VERSION"),
-            ))
-            .build();
-
         SlackClientBuilder {
             port: None,
             event_handler: None,
             slash_commands: Vec::new(),
             message_actions: Vec::new(),
-            client: client.unwrap(),
+            hears: Vec::new(),
+            modals: Vec::new(),
+            global_shortcuts: Vec::new(),
+            message_shortcuts: Vec::new(),
+            options_providers: Vec::new(),
+            middleware: Vec::new(),
+            error_handler: default_error_handler,
             signing_secret: None,
             token: None,
+            api_base_url: None,
+            retry_policy: RetryPolicy::default(),
+            data: TypeMap::new(),
+            concurrency: DEFAULT_CONCURRENCY,
+            user_cache_ttl: DEFAULT_USER_CACHE_TTL,
+        }
+    }
+
+    fn context(&self) -> Context {
+        Context {
+            api: self.api.clone(),
+            data: self.data.clone(),
+            bot: self.bot.clone(),
+            users: self.users.clone(),
         }
     }
 
@@ -89,86 +166,46 @@ where
 
         EVENTS.get_or_init(|| self.event_queue.0.clone());
 
-        tokio::task::spawn(listener::listen(self.port, self.signing_secret.clone()));
+        // needed to tell our own messages apart from everyone else's
+        match Bot::fetch(&self.context()).await {
+            Ok(bot) => self.bot = Some(Arc::new(bot)),
+            // `Hears` ignores every bot's messages without it, rather than risk answering itself
+            Err(err) => eprintln!("Failed to get bot identity: {:?}", err),
+        }
+
+        tokio::task::spawn(listener::listen(
+            self.port,
+            self.signing_secret.clone(),
+            self.users.clone(),
+        ));
 
+        let callback_queue = self.event_queue.0.clone();
         tokio::task::spawn(async move {
             loop {
-                self.event_queue.0.send(Events::Callback).await.unwrap();
+                callback_queue.send(Events::Callback).await.unwrap();
                 tokio::time::sleep(Duration::from_secs(60)).await;
             }
         });
 
         // event dispatcher
+        // every event gets its own task, so a slow handler doesn't hold up everything behind it
         loop {
             let event = self.event_queue.1.recv().await.unwrap();
-            match event {
-                Events::MemberJoinedChannel(event) => {
-                    self.event_handler
-                        .member_joined_channel(
-                            Context {
-                                client: &self.client,
-                                token: self.token.clone(),
-                            },
-                            event.channel,
-                            event.user,
-                            event.inviter,
-                        )
-                        .await
-                }
-                Events::SlashCommandCalled(event) => {
-                    let ctx = InteractionContext {
-                        ctx: Context {
-                            client: &self.client,
-                            token: self.token.clone(),
-                        },
-                        trigger_id: event.trigger_id,
-                    };
-
-                    println!("{} {}", event.command, &event.command[1..]);
-
-                    println!("{:#?}", self.slash_commands);
-
-                    if let Some(cmd) = self.slash_commands.get(&event.command[1..]) {
-                        cmd(ctx, event.text, event.user, event.channel).await;
-                    } else {
-                        println!("No command found for {}", event.command);
-                    }
-                }
-                Events::BlockMessageInteraction(event) => {
-                    let ctx: InteractionContext<'_> = InteractionContext {
-                        ctx: Context {
-                            client: &self.client,
-                            token: self.token.clone(),
-                        },
-                        trigger_id: event.trigger_id,
-                    };
-
-                    println!("{}", event.action);
-
-                    println!("{:#?}", self.message_actions);
-
-                    if let Some(cmd) = self.message_actions.get(&event.action) {
-                        cmd(
-                            ctx,
-                            event.user,
-                            event.username,
-                            event.display_name,
-                            event.channel,
-                        )
-                        .await;
-                    } else {
-                        println!("No action handler found for {}", event.action);
-                    }
-                }
-                Events::Callback => {
-                    self.event_handler
-                        .callback(&Context {
-                            client: &self.client,
-                            token: self.token.clone(),
-                        })
-                        .await
-                }
-            }
+
+            let ctx = self.context();
+            let dispatcher = self.dispatcher.clone();
+            let middleware = self.middleware.clone();
+            let concurrency = self.concurrency.clone();
+
+            // waiting for a permit here instead of in the loop keeps the queue drained, so the
+            // listener can still hand off requests Slack wants acknowledged quickly
+            tokio::task::spawn(async move {
+                let permit = concurrency.acquire_owned().await.unwrap();
+                Next::new(&middleware, dispatcher.as_ref())
+                    .run(ctx, event)
+                    .await;
+                drop(permit);
+            });
         }
     }
 }
@@ -181,14 +218,25 @@ where
     event_handler: Option<E>,
     slash_commands: Vec<SlashCommand>,
     message_actions: Vec<MessageAction>,
-    client: Client,
+    hears: Vec<Hears>,
+    modals: Vec<Arc<dyn Modal>>,
+    global_shortcuts: Vec<GlobalShortcut>,
+    message_shortcuts: Vec<MessageShortcut>,
+    options_providers: Vec<OptionsProvider>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    error_handler: ErrorHandlerFn,
     signing_secret: Option<String>,
     token: Option<String>,
+    api_base_url: Option<String>,
+    retry_policy: RetryPolicy,
+    data: TypeMap,
+    concurrency: usize,
+    user_cache_ttl: Duration,
 }
 
 impl<E> SlackClientBuilder<E>
 where
-    E: EventHandler + Send,
+    E: EventHandler,
 {
     pub fn port(mut self, port: u16) -> Self {
         self.port = Some(port);
@@ -210,6 +258,18 @@ where
         self
     }
 
+    /// Sends Web API calls somewhere other than `https://slack.com/api/`, like a local mock
+    pub fn api_base_url(mut self, url: &str) -> Self {
+        self.api_base_url = Some(url.to_string());
+        self
+    }
+
+    /// Sets how Web API calls are retried after being rate limited or failing to connect
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     pub fn slash_commands(mut self, commands: Vec<SlashCommand>) -> Self {
         self.slash_commands.extend(commands);
         self
@@ -230,45 +290,159 @@ where
         self
     }
 
+    /// Adds a listener that fires on messages matching its pattern
+    pub fn hears(mut self, hears: Hears) -> Self {
+        self.hears.push(hears);
+        self
+    }
+
+    pub fn global_shortcut(mut self, shortcut: GlobalShortcut) -> Self {
+        self.global_shortcuts.push(shortcut);
+        self
+    }
+
+    pub fn message_shortcut(mut self, shortcut: MessageShortcut) -> Self {
+        self.message_shortcuts.push(shortcut);
+        self
+    }
+
+    /// Adds what fills the `external_select`s with its `action_id`
+    pub fn options_provider(mut self, provider: OptionsProvider) -> Self {
+        self.options_providers.push(provider);
+        self
+    }
+
+    /// Adds a handler for submissions of the views with its `callback_id`
+    pub fn modal(mut self, modal: impl Modal) -> Self {
+        self.modals.push(Arc::new(modal));
+        self
+    }
+
+    /// Sets what happens when a command or action handler returns an error.
+    /// By default the error is logged and the user is told something went wrong.
+    pub fn error_handler(mut self, error_handler: ErrorHandlerFn) -> Self {
+        self.error_handler = error_handler;
+        self
+    }
+
+    /// Adds a middleware to the end of the pipeline.
+    /// Middleware runs in the order it is added, before events reach their handlers.
+    pub fn middleware(mut self, middleware: impl Middleware) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Adds a value to the shared state, which can be accessed with `ctx.data::<T>()`.
+    /// Only one value per type is stored, so adding the same type twice replaces the first one.
+    pub fn data<T>(mut self, value: T) -> Self
+    where
+        T: Any + Send + Sync,
+    {
+        self.data.insert(value);
+        self
+    }
+
+    /// The maximum number of events that are handled at the same time
+    pub fn max_concurrency(mut self, limit: usize) -> Self {
+        self.concurrency = limit.max(1);
+        self
+    }
+
+    /// How long `User::info` results are cached. A TTL of zero turns the cache off.
+    pub fn user_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.user_cache_ttl = ttl;
+        self
+    }
+
     pub fn build(self) -> SlackClient<E> {
         SlackClient {
             port: self.port.unwrap(),
-            event_handler: self.event_handler.unwrap(),
-            slash_commands: self
-                .slash_commands
-                .iter()
-                .fold(HashMap::new(), |mut map, cmd| {
-                    map.insert(cmd.command.clone(), cmd.execute);
-                    map
-                }),
-            message_actions: self
-                .message_actions
-                .iter()
-                .fold(HashMap::new(), |mut map, cmd| {
-                    map.insert(cmd.action.clone(), cmd.execute);
-                    map
-                }),
-            client: self.client,
-            event_queue: tokio::sync::mpsc::channel(3),
+            dispatcher: Arc::new(Dispatcher {
+                event_handler: self.event_handler.unwrap(),
+                slash_commands: self.slash_commands.into_iter().map(Arc::new).fold(
+                    HashMap::new(),
+                    |mut map, cmd| {
+                        for alias in &cmd.aliases {
+                            map.insert(alias.to_lowercase(), cmd.clone());
+                        }
+                        map.insert(cmd.command.to_lowercase(), cmd);
+                        map
+                    },
+                ),
+                message_actions: self.message_actions,
+                hears: self.hears,
+                modals: self
+                    .modals
+                    .into_iter()
+                    .map(|modal| (modal.callback_id().to_string(), modal))
+                    .collect(),
+                options_providers: self
+                    .options_providers
+                    .into_iter()
+                    .map(|provider| (provider.action_id.clone(), provider))
+                    .collect(),
+                global_shortcuts: self
+                    .global_shortcuts
+                    .into_iter()
+                    .map(|shortcut| (shortcut.callback_id.clone(), shortcut))
+                    .collect(),
+                message_shortcuts: self
+                    .message_shortcuts
+                    .into_iter()
+                    .map(|shortcut| (shortcut.callback_id.clone(), shortcut))
+                    .collect(),
+                error_handler: self.error_handler,
+            }),
+            middleware: Arc::new(self.middleware),
+            event_queue: tokio::sync::mpsc::channel(EVENT_QUEUE_CAPACITY),
             signing_secret: self.signing_secret.unwrap(),
-            token: self.token.unwrap(),
+            api: SlackApi::new(self.token.unwrap())
+                .base_url(self.api_base_url.as_deref().unwrap_or(DEFAULT_BASE_URL))
+                .retry_policy(self.retry_policy),
+            data: Arc::new(self.data),
+            concurrency: Arc::new(Semaphore::new(self.concurrency)),
+            bot: None,
+            users: Arc::new(UserCache::new(self.user_cache_ttl)),
         }
     }
 }
 
 /// Holds stuff needed to interact with the Slack API
+/// This is cheap to clone, so it can be moved into spawned tasks.
 #[derive(Debug, Clone)]
-pub struct Context<'a> {
-    pub(super) client: &'a Client,
-    pub(super) token: String,
+pub struct Context {
+    pub(super) api: SlackApi,
+    pub(super) data: Arc<TypeMap>,
+    pub(super) bot: Option<Arc<Bot>>,
+    pub(super) users: Arc<UserCache>,
+}
+
+impl Context {
+    /// The client for calling Web API methods directly
+    pub fn api(&self) -> &SlackApi {
+        &self.api
+    }
+
+    /// Gets a value from the shared state added with `SlackClientBuilder::data`
+    pub fn data<T>(&self) -> Option<Arc<T>>
+    where
+        T: Any + Send + Sync,
+    {
+        self.data.get::<T>()
+    }
+
+    /// The bot crow is running as, if it could be looked up when the client started
+    pub fn bot(&self) -> Option<&Bot> {
+        self.bot.as_deref()
+    }
 }
 
-impl<'a> ContextSupplier<'a> for Context<'a> {
-    fn get_context(&self) -> &Context<'a> {
+impl ContextSupplier for Context {
+    fn get_context(&self) -> &Context {
         self
     }
 }
 
-pub trait ContextSupplier<'a> {
-    fn get_context(&self) -> &Context<'a>;
+pub trait ContextSupplier {
+    fn get_context(&self) -> &Context;
 }
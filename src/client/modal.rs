@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use futures::{future::BoxFuture, FutureExt};
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+use super::{
+    interactions::InteractionContext,
+    model::view::{View, ViewInfo},
+};
+
+/// Handles the views with a certain `callback_id`.
+///
+/// ```ignore
+/// struct FeedbackModal;
+///
+/// impl Modal for FeedbackModal {
+///     fn callback_id(&self) -> &str {
+///         "feedback"
+///     }
+///
+///     fn submit<'a>(&'a self, ctx: InteractionContext, view: ViewInfo) -> BoxFuture<'a, ViewResponse> {
+///         async move {
+///             match view.state.text("feedback", "text") {
+///                 Some(text) if text.len() > 10 => ViewResponse::Close,
+///                 _ => ViewResponse::error("feedback", "Tell us a bit more!"),
+///             }
+///         }
+///         .boxed()
+///     }
+/// }
+/// ```
+pub trait Modal: Send + Sync + 'static {
+    fn callback_id(&self) -> &str;
+
+    /// Called when the user submits the view, and has to answer within 3 seconds
+    fn submit<'a>(&'a self, ctx: InteractionContext, view: ViewInfo)
+        -> BoxFuture<'a, ViewResponse>;
+
+    /// Called when the user closes the view, if it was opened with `notify_on_close`
+    fn closed<'a>(&'a self, _ctx: InteractionContext, _view: ViewInfo) -> BoxFuture<'a, ()> {
+        async {}.boxed()
+    }
+}
+
+/// What happens to the view after it was submitted
+#[derive(Debug, Clone, Default)]
+pub enum ViewResponse {
+    /// Close the view
+    #[default]
+    Close,
+    /// Keep the view open and show errors next to the inputs, by block ID
+    Errors(HashMap<String, String>),
+    /// Replace the view with a new one
+    Update(View),
+    /// Put a new view on top of the stack
+    Push(View),
+    /// Close every view in the stack
+    Clear,
+}
+
+impl ViewResponse {
+    /// Keeps the view open with an error next to one input
+    pub fn error(block_id: &str, error: &str) -> Self {
+        ViewResponse::Errors(HashMap::from([(block_id.to_string(), error.to_string())]))
+    }
+}
+
+impl Serialize for ViewResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        match self {
+            ViewResponse::Close => {}
+            ViewResponse::Errors(errors) => {
+                map.serialize_entry("response_action", "errors")?;
+                map.serialize_entry("errors", errors)?;
+            }
+            ViewResponse::Update(view) => {
+                map.serialize_entry("response_action", "update")?;
+                map.serialize_entry("view", view)?;
+            }
+            ViewResponse::Push(view) => {
+                map.serialize_entry("response_action", "push")?;
+                map.serialize_entry("view", view)?;
+            }
+            ViewResponse::Clear => {
+                map.serialize_entry("response_action", "clear")?;
+            }
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, to_value};
+
+    use super::*;
+
+    #[test]
+    fn close_is_an_empty_body() {
+        assert_eq!(to_value(ViewResponse::Close).unwrap(), json!({}));
+    }
+
+    #[test]
+    fn errors_are_keyed_by_block_id() {
+        assert_eq!(
+            to_value(ViewResponse::error("feedback", "Tell us a bit more!")).unwrap(),
+            json!({
+                "response_action": "errors",
+                "errors": { "feedback": "Tell us a bit more!" },
+            })
+        );
+    }
+
+    #[test]
+    fn update_and_push_carry_the_view() {
+        let view = View::modal("Thanks");
+        let expected = to_value(&view).unwrap();
+        assert_eq!(expected["type"], "modal");
+
+        assert_eq!(
+            to_value(ViewResponse::Update(view.clone())).unwrap(),
+            json!({ "response_action": "update", "view": expected })
+        );
+        assert_eq!(
+            to_value(ViewResponse::Push(view)).unwrap(),
+            json!({ "response_action": "push", "view": expected })
+        );
+    }
+
+    #[test]
+    fn clear_has_no_view() {
+        assert_eq!(
+            to_value(ViewResponse::Clear).unwrap(),
+            json!({ "response_action": "clear" })
+        );
+    }
+}
@@ -0,0 +1,997 @@
+use serde::{ser::SerializeMap, Serialize, Serializer};
+
+use super::{
+    check_count, check_length,
+    text::{Confirm, OptionGroup, PlainText, SelectOption, Style},
+};
+use crate::client::{Channel, SlackError, User};
+
+/// An interactive element, used in actions and input blocks or as the accessory of a section
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum Element {
+    Button(Button),
+    StaticSelect(StaticSelect),
+    ExternalSelect(ExternalSelect),
+    UsersSelect(UsersSelect),
+    ConversationsSelect(ConversationsSelect),
+    ChannelsSelect(ChannelsSelect),
+    Overflow(Overflow),
+    DatePicker(DatePicker),
+    TimePicker(TimePicker),
+    DatetimePicker(DatetimePicker),
+    Checkboxes(Checkboxes),
+    RadioButtons(RadioButtons),
+    TextInput(TextInput),
+    NumberInput(NumberInput),
+    Image(ImageElement),
+}
+
+impl Element {
+    pub(crate) fn validate(&self) -> Result<(), SlackError> {
+        match self {
+            Element::Button(button) => button.validate(),
+            Element::StaticSelect(select) => select.validate(),
+            Element::ExternalSelect(select) => select.validate(),
+            Element::UsersSelect(select) => select.validate(),
+            Element::ConversationsSelect(select) => select.validate(),
+            Element::ChannelsSelect(select) => select.validate(),
+            Element::Overflow(overflow) => overflow.validate(),
+            Element::DatePicker(picker) => picker.validate(),
+            Element::TimePicker(picker) => picker.validate(),
+            Element::DatetimePicker(picker) => picker.validate(),
+            Element::Checkboxes(checkboxes) => checkboxes.validate(),
+            Element::RadioButtons(buttons) => buttons.validate(),
+            Element::TextInput(input) => input.validate(),
+            Element::NumberInput(input) => input.validate(),
+            Element::Image(image) => image.validate(),
+        }
+    }
+}
+
+macro_rules! impl_element {
+    ($($element:ident),*) => {
+        $(
+            impl From<$element> for Element {
+                fn from(element: $element) -> Self {
+                    Element::$element(element)
+                }
+            }
+        )*
+    };
+}
+
+impl_element!(
+    Button,
+    StaticSelect,
+    ExternalSelect,
+    UsersSelect,
+    ConversationsSelect,
+    ChannelsSelect,
+    Overflow,
+    DatePicker,
+    TimePicker,
+    DatetimePicker,
+    Checkboxes,
+    RadioButtons,
+    TextInput,
+    NumberInput
+);
+
+impl From<ImageElement> for Element {
+    fn from(image: ImageElement) -> Self {
+        Element::Image(image)
+    }
+}
+
+/// Setters every element with these fields shares
+macro_rules! impl_common_setters {
+    (placeholder) => {
+        /// Shown while nothing is picked or entered
+        pub fn placeholder(mut self, placeholder: &str) -> Self {
+            self.placeholder = Some(PlainText::new(placeholder));
+            self
+        }
+    };
+    (confirm) => {
+        /// Asks the user to confirm before anything happens
+        pub fn confirm(mut self, confirm: Confirm) -> Self {
+            self.confirm = Some(confirm);
+            self
+        }
+    };
+    (focus_on_load) => {
+        /// Focuses the element when the view is opened. Only one element in a view can do this.
+        pub fn focus_on_load(mut self) -> Self {
+            self.focus_on_load = Some(true);
+            self
+        }
+    };
+    (max_selected_items) => {
+        /// Lets the user pick more than one item, up to `max` if it's not 0
+        pub fn multi(mut self, max: u32) -> Self {
+            self.ty = Self::MULTI;
+            self.initial.multi = true;
+            self.max_selected_items = (max > 0).then_some(max);
+            self
+        }
+    };
+    ($($setter:ident),+) => {
+        $(impl_common_setters!($setter);)+
+    };
+}
+
+/// What a select starts with. Kept whole until it's sent, so it doesn't matter whether `initial`
+/// or `multi` is called first.
+#[derive(Debug, Clone)]
+struct Initial<T> {
+    single: &'static str,
+    plural: &'static str,
+    multi: bool,
+    values: Vec<T>,
+}
+
+impl<T> Initial<T> {
+    fn new(single: &'static str, plural: &'static str) -> Self {
+        Self {
+            single,
+            plural,
+            multi: false,
+            values: Vec::new(),
+        }
+    }
+}
+
+impl<T> Serialize for Initial<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        if self.multi {
+            if !self.values.is_empty() {
+                map.serialize_entry(self.plural, &self.values)?;
+            }
+        } else if let Some(value) = self.values.first() {
+            map.serialize_entry(self.single, value)?;
+        }
+        map.end()
+    }
+}
+
+fn validate_common(
+    action_id: &str,
+    placeholder: &Option<PlainText>,
+    confirm: &Option<Confirm>,
+) -> Result<(), SlackError> {
+    check_length("action_id", action_id, 255)?;
+    if let Some(placeholder) = placeholder {
+        placeholder.validate("placeholder", 150)?;
+    }
+    if let Some(confirm) = confirm {
+        confirm.validate()?;
+    }
+    Ok(())
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Button {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    text: PlainText,
+    action_id: String,
+    url: Option<String>,
+    value: Option<String>,
+    style: Option<Style>,
+    confirm: Option<Confirm>,
+    accessibility_label: Option<String>,
+}
+
+impl Button {
+    pub fn new(text: &str, action_id: &str) -> Self {
+        Self {
+            ty: "button",
+            text: PlainText::new(text),
+            action_id: action_id.to_string(),
+            url: None,
+            value: None,
+            style: None,
+            confirm: None,
+            accessibility_label: None,
+        }
+    }
+
+    /// Sent along with the action
+    pub fn value(mut self, value: &str) -> Self {
+        self.value = Some(value.to_string());
+        self
+    }
+
+    /// Opens the URL in the user's browser. The action is still sent.
+    pub fn url(mut self, url: &str) -> Self {
+        self.url = Some(url.to_string());
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Read by screen readers instead of the text
+    pub fn accessibility_label(mut self, label: &str) -> Self {
+        self.accessibility_label = Some(label.to_string());
+        self
+    }
+
+    impl_common_setters!(confirm);
+
+    fn validate(&self) -> Result<(), SlackError> {
+        self.text.validate("button text", 75)?;
+        validate_common(&self.action_id, &None, &self.confirm)?;
+        if let Some(url) = &self.url {
+            check_length("button url", url, 3000)?;
+        }
+        if let Some(value) = &self.value {
+            check_length("button value", value, 2000)?;
+        }
+        if let Some(label) = &self.accessibility_label {
+            check_length("accessibility label", label, 75)?;
+        }
+        Ok(())
+    }
+}
+
+/// A select menu with a fixed list of options
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct StaticSelect {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    action_id: String,
+    placeholder: Option<PlainText>,
+    options: Option<Vec<SelectOption>>,
+    option_groups: Option<Vec<OptionGroup>>,
+    #[serde(flatten)]
+    initial: Initial<SelectOption>,
+    max_selected_items: Option<u32>,
+    confirm: Option<Confirm>,
+    focus_on_load: Option<bool>,
+}
+
+impl StaticSelect {
+    const MULTI: &'static str = "multi_static_select";
+
+    pub fn new(action_id: &str, options: Vec<SelectOption>) -> Self {
+        Self {
+            ty: "static_select",
+            action_id: action_id.to_string(),
+            placeholder: None,
+            options: Some(options),
+            option_groups: None,
+            initial: Initial::new("initial_option", "initial_options"),
+            max_selected_items: None,
+            confirm: None,
+            focus_on_load: None,
+        }
+    }
+
+    pub fn grouped(action_id: &str, groups: Vec<OptionGroup>) -> Self {
+        Self {
+            options: None,
+            option_groups: Some(groups),
+            ..Self::new(action_id, Vec::new())
+        }
+    }
+
+    /// The options that are picked to begin with. Only the first is used unless it's a multi
+    /// select.
+    pub fn initial(mut self, options: Vec<SelectOption>) -> Self {
+        self.initial.values = options;
+        self
+    }
+
+    impl_common_setters!(placeholder, confirm, focus_on_load, max_selected_items);
+
+    fn validate(&self) -> Result<(), SlackError> {
+        validate_common(&self.action_id, &self.placeholder, &self.confirm)?;
+        if let Some(options) = &self.options {
+            check_count("options in a select", options.len(), 100)?;
+            options.iter().try_for_each(SelectOption::validate)?;
+        }
+        if let Some(groups) = &self.option_groups {
+            check_count("option groups in a select", groups.len(), 100)?;
+            groups.iter().try_for_each(OptionGroup::validate)?;
+        }
+        Ok(())
+    }
+}
+
+/// A select menu whose options come from an `OptionsProvider`
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalSelect {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    action_id: String,
+    placeholder: Option<PlainText>,
+    #[serde(flatten)]
+    initial: Initial<SelectOption>,
+    min_query_length: Option<u32>,
+    max_selected_items: Option<u32>,
+    confirm: Option<Confirm>,
+    focus_on_load: Option<bool>,
+}
+
+impl ExternalSelect {
+    const MULTI: &'static str = "multi_external_select";
+
+    pub fn new(action_id: &str) -> Self {
+        Self {
+            ty: "external_select",
+            action_id: action_id.to_string(),
+            placeholder: None,
+            initial: Initial::new("initial_option", "initial_options"),
+            min_query_length: None,
+            max_selected_items: None,
+            confirm: None,
+            focus_on_load: None,
+        }
+    }
+
+    /// How many characters have to be typed before options are asked for
+    pub fn min_query_length(mut self, length: u32) -> Self {
+        self.min_query_length = Some(length);
+        self
+    }
+
+    /// The options that are picked to begin with. Only the first is used unless it's a multi
+    /// select.
+    pub fn initial(mut self, options: Vec<SelectOption>) -> Self {
+        self.initial.values = options;
+        self
+    }
+
+    impl_common_setters!(placeholder, confirm, focus_on_load, max_selected_items);
+
+    fn validate(&self) -> Result<(), SlackError> {
+        validate_common(&self.action_id, &self.placeholder, &self.confirm)
+    }
+}
+
+/// A select menu of the users in the workspace
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct UsersSelect {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    action_id: String,
+    placeholder: Option<PlainText>,
+    #[serde(flatten)]
+    initial: Initial<User>,
+    max_selected_items: Option<u32>,
+    confirm: Option<Confirm>,
+    focus_on_load: Option<bool>,
+}
+
+impl UsersSelect {
+    const MULTI: &'static str = "multi_users_select";
+
+    pub fn new(action_id: &str) -> Self {
+        Self {
+            ty: "users_select",
+            action_id: action_id.to_string(),
+            placeholder: None,
+            initial: Initial::new("initial_user", "initial_users"),
+            max_selected_items: None,
+            confirm: None,
+            focus_on_load: None,
+        }
+    }
+
+    /// The users that are picked to begin with. Only the first is used unless it's a multi
+    /// select.
+    pub fn initial(mut self, users: Vec<User>) -> Self {
+        self.initial.values = users;
+        self
+    }
+
+    impl_common_setters!(placeholder, confirm, focus_on_load, max_selected_items);
+
+    fn validate(&self) -> Result<(), SlackError> {
+        validate_common(&self.action_id, &self.placeholder, &self.confirm)
+    }
+}
+
+/// Which conversations a conversation select lists
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConversationFilter {
+    include: Option<Vec<&'static str>>,
+    exclude_external_shared_channels: Option<bool>,
+    exclude_bot_users: Option<bool>,
+}
+
+impl ConversationFilter {
+    /// Only list these kinds of conversations: `im`, `mpim`, `private` or `public`
+    pub fn include(mut self, kinds: Vec<&'static str>) -> Self {
+        self.include = Some(kinds);
+        self
+    }
+
+    pub fn exclude_external_shared_channels(mut self) -> Self {
+        self.exclude_external_shared_channels = Some(true);
+        self
+    }
+
+    pub fn exclude_bot_users(mut self) -> Self {
+        self.exclude_bot_users = Some(true);
+        self
+    }
+}
+
+/// A select menu of channels, DMs and group DMs
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct ConversationsSelect {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    action_id: String,
+    placeholder: Option<PlainText>,
+    #[serde(flatten)]
+    initial: Initial<Channel>,
+    default_to_current_conversation: Option<bool>,
+    filter: Option<ConversationFilter>,
+    response_url_enabled: Option<bool>,
+    max_selected_items: Option<u32>,
+    confirm: Option<Confirm>,
+    focus_on_load: Option<bool>,
+}
+
+impl ConversationsSelect {
+    const MULTI: &'static str = "multi_conversations_select";
+
+    pub fn new(action_id: &str) -> Self {
+        Self {
+            ty: "conversations_select",
+            action_id: action_id.to_string(),
+            placeholder: None,
+            initial: Initial::new("initial_conversation", "initial_conversations"),
+            default_to_current_conversation: None,
+            filter: None,
+            response_url_enabled: None,
+            max_selected_items: None,
+            confirm: None,
+            focus_on_load: None,
+        }
+    }
+
+    /// The conversations that are picked to begin with. Only the first is used unless it's a
+    /// multi select.
+    pub fn initial(mut self, conversations: Vec<Channel>) -> Self {
+        self.initial.values = conversations;
+        self
+    }
+
+    /// Pick the conversation the modal was opened from to begin with
+    pub fn default_to_current_conversation(mut self) -> Self {
+        self.default_to_current_conversation = Some(true);
+        self
+    }
+
+    pub fn filter(mut self, filter: ConversationFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Sends a `response_url` for the picked conversation with the view submission.
+    /// Only works in input blocks of modals.
+    pub fn response_url_enabled(mut self) -> Self {
+        self.response_url_enabled = Some(true);
+        self
+    }
+
+    impl_common_setters!(placeholder, confirm, focus_on_load, max_selected_items);
+
+    fn validate(&self) -> Result<(), SlackError> {
+        validate_common(&self.action_id, &self.placeholder, &self.confirm)
+    }
+}
+
+/// A select menu of public channels
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct ChannelsSelect {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    action_id: String,
+    placeholder: Option<PlainText>,
+    #[serde(flatten)]
+    initial: Initial<Channel>,
+    response_url_enabled: Option<bool>,
+    max_selected_items: Option<u32>,
+    confirm: Option<Confirm>,
+    focus_on_load: Option<bool>,
+}
+
+impl ChannelsSelect {
+    const MULTI: &'static str = "multi_channels_select";
+
+    pub fn new(action_id: &str) -> Self {
+        Self {
+            ty: "channels_select",
+            action_id: action_id.to_string(),
+            placeholder: None,
+            initial: Initial::new("initial_channel", "initial_channels"),
+            response_url_enabled: None,
+            max_selected_items: None,
+            confirm: None,
+            focus_on_load: None,
+        }
+    }
+
+    /// The channels that are picked to begin with. Only the first is used unless it's a multi
+    /// select.
+    pub fn initial(mut self, channels: Vec<Channel>) -> Self {
+        self.initial.values = channels;
+        self
+    }
+
+    /// Sends a `response_url` for the picked channel with the view submission.
+    /// Only works in input blocks of modals.
+    pub fn response_url_enabled(mut self) -> Self {
+        self.response_url_enabled = Some(true);
+        self
+    }
+
+    impl_common_setters!(placeholder, confirm, focus_on_load, max_selected_items);
+
+    fn validate(&self) -> Result<(), SlackError> {
+        validate_common(&self.action_id, &self.placeholder, &self.confirm)
+    }
+}
+
+/// A `...` button that opens a menu of 2 to 5 options
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Overflow {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    action_id: String,
+    options: Vec<SelectOption>,
+    confirm: Option<Confirm>,
+}
+
+impl Overflow {
+    pub fn new(action_id: &str, options: Vec<SelectOption>) -> Self {
+        Self {
+            ty: "overflow",
+            action_id: action_id.to_string(),
+            options,
+            confirm: None,
+        }
+    }
+
+    impl_common_setters!(confirm);
+
+    fn validate(&self) -> Result<(), SlackError> {
+        validate_common(&self.action_id, &None, &self.confirm)?;
+        if self.options.len() < 2 {
+            return Err(SlackError::InvalidInput(
+                "an overflow menu needs at least 2 options".to_string(),
+            ));
+        }
+        check_count("options in an overflow menu", self.options.len(), 5)?;
+        self.options.iter().try_for_each(SelectOption::validate)
+    }
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct DatePicker {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    action_id: String,
+    placeholder: Option<PlainText>,
+    initial_date: Option<String>,
+    confirm: Option<Confirm>,
+    focus_on_load: Option<bool>,
+}
+
+impl DatePicker {
+    pub fn new(action_id: &str) -> Self {
+        Self {
+            ty: "datepicker",
+            action_id: action_id.to_string(),
+            placeholder: None,
+            initial_date: None,
+            confirm: None,
+            focus_on_load: None,
+        }
+    }
+
+    /// The date picked to begin with, as `YYYY-MM-DD`
+    pub fn initial_date(mut self, date: &str) -> Self {
+        self.initial_date = Some(date.to_string());
+        self
+    }
+
+    impl_common_setters!(placeholder, confirm, focus_on_load);
+
+    fn validate(&self) -> Result<(), SlackError> {
+        validate_common(&self.action_id, &self.placeholder, &self.confirm)
+    }
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct TimePicker {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    action_id: String,
+    placeholder: Option<PlainText>,
+    initial_time: Option<String>,
+    timezone: Option<String>,
+    confirm: Option<Confirm>,
+    focus_on_load: Option<bool>,
+}
+
+impl TimePicker {
+    pub fn new(action_id: &str) -> Self {
+        Self {
+            ty: "timepicker",
+            action_id: action_id.to_string(),
+            placeholder: None,
+            initial_time: None,
+            timezone: None,
+            confirm: None,
+            focus_on_load: None,
+        }
+    }
+
+    /// The time picked to begin with, as `HH:mm`
+    pub fn initial_time(mut self, time: &str) -> Self {
+        self.initial_time = Some(time.to_string());
+        self
+    }
+
+    /// The IANA timezone the time is in, like `Europe/London`. Defaults to the user's timezone.
+    pub fn timezone(mut self, timezone: &str) -> Self {
+        self.timezone = Some(timezone.to_string());
+        self
+    }
+
+    impl_common_setters!(placeholder, confirm, focus_on_load);
+
+    fn validate(&self) -> Result<(), SlackError> {
+        validate_common(&self.action_id, &self.placeholder, &self.confirm)
+    }
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct DatetimePicker {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    action_id: String,
+    initial_date_time: Option<i64>,
+    confirm: Option<Confirm>,
+    focus_on_load: Option<bool>,
+}
+
+impl DatetimePicker {
+    pub fn new(action_id: &str) -> Self {
+        Self {
+            ty: "datetimepicker",
+            action_id: action_id.to_string(),
+            initial_date_time: None,
+            confirm: None,
+            focus_on_load: None,
+        }
+    }
+
+    /// The time picked to begin with, as a UNIX timestamp
+    pub fn initial_date_time(mut self, timestamp: i64) -> Self {
+        self.initial_date_time = Some(timestamp);
+        self
+    }
+
+    impl_common_setters!(confirm, focus_on_load);
+
+    fn validate(&self) -> Result<(), SlackError> {
+        validate_common(&self.action_id, &None, &self.confirm)
+    }
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Checkboxes {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    action_id: String,
+    options: Vec<SelectOption>,
+    initial_options: Option<Vec<SelectOption>>,
+    confirm: Option<Confirm>,
+    focus_on_load: Option<bool>,
+}
+
+impl Checkboxes {
+    pub fn new(action_id: &str, options: Vec<SelectOption>) -> Self {
+        Self {
+            ty: "checkboxes",
+            action_id: action_id.to_string(),
+            options,
+            initial_options: None,
+            confirm: None,
+            focus_on_load: None,
+        }
+    }
+
+    /// The options that are checked to begin with
+    pub fn initial(mut self, options: Vec<SelectOption>) -> Self {
+        self.initial_options = Some(options);
+        self
+    }
+
+    impl_common_setters!(confirm, focus_on_load);
+
+    fn validate(&self) -> Result<(), SlackError> {
+        validate_common(&self.action_id, &None, &self.confirm)?;
+        check_count("checkboxes", self.options.len(), 10)?;
+        self.options.iter().try_for_each(SelectOption::validate)
+    }
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct RadioButtons {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    action_id: String,
+    options: Vec<SelectOption>,
+    initial_option: Option<SelectOption>,
+    confirm: Option<Confirm>,
+    focus_on_load: Option<bool>,
+}
+
+impl RadioButtons {
+    pub fn new(action_id: &str, options: Vec<SelectOption>) -> Self {
+        Self {
+            ty: "radio_buttons",
+            action_id: action_id.to_string(),
+            options,
+            initial_option: None,
+            confirm: None,
+            focus_on_load: None,
+        }
+    }
+
+    /// The option that's picked to begin with
+    pub fn initial(mut self, option: SelectOption) -> Self {
+        self.initial_option = Some(option);
+        self
+    }
+
+    impl_common_setters!(confirm, focus_on_load);
+
+    fn validate(&self) -> Result<(), SlackError> {
+        validate_common(&self.action_id, &None, &self.confirm)?;
+        check_count("radio buttons", self.options.len(), 10)?;
+        self.options.iter().try_for_each(SelectOption::validate)
+    }
+}
+
+/// A text, email or URL input
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct TextInput {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    action_id: String,
+    placeholder: Option<PlainText>,
+    initial_value: Option<String>,
+    multiline: Option<bool>,
+    min_length: Option<u32>,
+    max_length: Option<u32>,
+    focus_on_load: Option<bool>,
+}
+
+impl TextInput {
+    pub fn new(action_id: &str) -> Self {
+        Self {
+            ty: "plain_text_input",
+            action_id: action_id.to_string(),
+            placeholder: None,
+            initial_value: None,
+            multiline: None,
+            min_length: None,
+            max_length: None,
+            focus_on_load: None,
+        }
+    }
+
+    pub fn email(action_id: &str) -> Self {
+        Self {
+            ty: "email_text_input",
+            ..Self::new(action_id)
+        }
+    }
+
+    pub fn url(action_id: &str) -> Self {
+        Self {
+            ty: "url_text_input",
+            ..Self::new(action_id)
+        }
+    }
+
+    pub fn initial_value(mut self, value: &str) -> Self {
+        self.initial_value = Some(value.to_string());
+        self
+    }
+
+    /// Shows a bigger box for the text. Only for text inputs.
+    pub fn multiline(mut self) -> Self {
+        self.multiline = Some(true);
+        self
+    }
+
+    /// Limits how much can be entered. Only for text inputs.
+    pub fn length(mut self, min: u32, max: u32) -> Self {
+        self.min_length = Some(min);
+        self.max_length = Some(max);
+        self
+    }
+
+    impl_common_setters!(placeholder, focus_on_load);
+
+    fn validate(&self) -> Result<(), SlackError> {
+        validate_common(&self.action_id, &self.placeholder, &None)?;
+        if self.ty != "plain_text_input"
+            && (self.multiline.is_some() || self.min_length.is_some() || self.max_length.is_some())
+        {
+            return Err(SlackError::InvalidInput(format!(
+                "{} can't be multiline or have a length",
+                self.ty
+            )));
+        }
+        if self.min_length.unwrap_or(0) > 3000 || self.max_length.unwrap_or(0) > 3000 {
+            return Err(SlackError::InvalidInput(
+                "text inputs can be at most 3000 characters long".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct NumberInput {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    action_id: String,
+    is_decimal_allowed: bool,
+    placeholder: Option<PlainText>,
+    initial_value: Option<String>,
+    min_value: Option<String>,
+    max_value: Option<String>,
+    focus_on_load: Option<bool>,
+}
+
+impl NumberInput {
+    pub fn new(action_id: &str) -> Self {
+        Self {
+            ty: "number_input",
+            action_id: action_id.to_string(),
+            is_decimal_allowed: false,
+            placeholder: None,
+            initial_value: None,
+            min_value: None,
+            max_value: None,
+            focus_on_load: None,
+        }
+    }
+
+    pub fn decimal(mut self) -> Self {
+        self.is_decimal_allowed = true;
+        self
+    }
+
+    pub fn initial_value(mut self, value: f64) -> Self {
+        self.initial_value = Some(value.to_string());
+        self
+    }
+
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.min_value = Some(min.to_string());
+        self.max_value = Some(max.to_string());
+        self
+    }
+
+    impl_common_setters!(placeholder, focus_on_load);
+
+    fn validate(&self) -> Result<(), SlackError> {
+        validate_common(&self.action_id, &self.placeholder, &None)
+    }
+}
+
+/// An image shown inside a section or context block
+#[derive(Debug, Clone, Serialize)]
+pub struct ImageElement {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    image_url: String,
+    alt_text: String,
+}
+
+impl ImageElement {
+    pub fn new(image_url: &str, alt_text: &str) -> Self {
+        Self {
+            ty: "image",
+            image_url: image_url.to_string(),
+            alt_text: alt_text.to_string(),
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), SlackError> {
+        check_length("image url", &self.image_url, 3000)?;
+        check_length("alt text", &self.alt_text, 2000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn options() -> Vec<SelectOption> {
+        vec![
+            SelectOption::new("Red", "red"),
+            SelectOption::new("Blue", "blue"),
+        ]
+    }
+
+    #[test]
+    fn multi_select_initial_options_dont_depend_on_call_order() {
+        let before = serde_json::to_value(
+            StaticSelect::new("color", options())
+                .initial(options())
+                .multi(2),
+        )
+        .unwrap();
+        let after = serde_json::to_value(
+            StaticSelect::new("color", options())
+                .multi(2)
+                .initial(options()),
+        )
+        .unwrap();
+
+        assert_eq!(before, after);
+        assert_eq!(before["type"], "multi_static_select");
+        assert_eq!(before["initial_options"][1]["value"], "blue");
+        assert_eq!(before.get("initial_option"), None);
+    }
+
+    #[test]
+    fn single_select_uses_the_first_initial_value() {
+        let select = serde_json::to_value(UsersSelect::new("who").initial(vec![
+            User::new("U1".to_string()),
+            User::new("U2".to_string()),
+        ]))
+        .unwrap();
+
+        assert_eq!(select["type"], "users_select");
+        assert_eq!(select["initial_user"], "U1");
+        assert_eq!(select.get("initial_users"), None);
+
+        let select = serde_json::to_value(ChannelsSelect::new("where").multi(0)).unwrap();
+        assert_eq!(
+            select,
+            json!({"type": "multi_channels_select", "action_id": "where"})
+        );
+    }
+}
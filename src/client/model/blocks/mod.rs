@@ -0,0 +1,622 @@
+//! Typed Block Kit blocks, for building messages and views.
+//!
+//! ```ignore
+//! Message::new().blocks(vec![
+//!     Section::new("Do ye wish t' introduce yourself t' the crew?").into(),
+//!     Actions::new(vec![Button::new("announce yer presence", "announce_presence")
+//!         .style(Style::Primary)
+//!         .into()])
+//!     .into(),
+//! ])
+//! ```
+//!
+//! Slack's limits on text lengths and element counts are checked before anything is sent, so
+//! mistakes show up as a `SlackError::InvalidInput` instead of a rejected request.
+
+use serde::Serialize;
+
+use crate::client::SlackError;
+
+pub mod elements;
+pub mod rich_text;
+pub mod text;
+
+pub use elements::*;
+pub use rich_text::{Inline, ListStyle, RichTextElement, TextStyle};
+pub use text::{Confirm, OptionGroup, PlainText, SelectOption, Style, Text};
+
+/// How many blocks a message can have
+pub const MAX_MESSAGE_BLOCKS: usize = 50;
+/// How many blocks a modal or App Home can have
+pub const MAX_VIEW_BLOCKS: usize = 100;
+
+pub(crate) fn check_length(what: &str, text: &str, max: usize) -> Result<(), SlackError> {
+    let length = text.chars().count();
+    if length > max {
+        return Err(SlackError::InvalidInput(format!(
+            "{} can be at most {} characters long, but is {}",
+            what, max, length
+        )));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_count(what: &str, count: usize, max: usize) -> Result<(), SlackError> {
+    if count > max {
+        return Err(SlackError::InvalidInput(format!(
+            "there can be at most {} {}, but there are {}",
+            max, what, count
+        )));
+    }
+
+    Ok(())
+}
+
+/// Checks every block, and that there aren't more than `max` of them
+pub(crate) fn validate_blocks(blocks: &[Block], max: usize) -> Result<(), SlackError> {
+    check_count("blocks", blocks.len(), max)?;
+    blocks.iter().try_for_each(Block::validate)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum Block {
+    Section(Section),
+    Context(Context),
+    Divider(Divider),
+    Header(Header),
+    Image(Image),
+    Actions(Actions),
+    Input(Input),
+    RichText(RichText),
+    Video(Video),
+    File(File),
+}
+
+impl Block {
+    pub fn validate(&self) -> Result<(), SlackError> {
+        match self {
+            Block::Section(section) => section.validate(),
+            Block::Context(context) => context.validate(),
+            Block::Divider(divider) => check_block_id(&divider.block_id),
+            Block::Header(header) => header.validate(),
+            Block::Image(image) => image.validate(),
+            Block::Actions(actions) => actions.validate(),
+            Block::Input(input) => input.validate(),
+            Block::RichText(rich_text) => check_block_id(&rich_text.block_id),
+            Block::Video(video) => video.validate(),
+            Block::File(file) => check_block_id(&file.block_id),
+        }
+    }
+}
+
+macro_rules! impl_block {
+    ($($block:ident),*) => {
+        $(
+            impl From<$block> for Block {
+                fn from(block: $block) -> Self {
+                    Block::$block(block)
+                }
+            }
+
+            impl $block {
+                /// Used to tell which block an action or input came from
+                pub fn block_id(mut self, block_id: &str) -> Self {
+                    self.block_id = Some(block_id.to_string());
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_block!(Section, Context, Divider, Header, Image, Actions, Input, RichText, Video, File);
+
+fn check_block_id(block_id: &Option<String>) -> Result<(), SlackError> {
+    match block_id {
+        Some(block_id) => check_length("block_id", block_id, 255),
+        None => Ok(()),
+    }
+}
+
+/// Text, optionally with fields next to each other and an element on the side
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Section {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    text: Option<Text>,
+    fields: Option<Vec<Text>>,
+    accessory: Option<Element>,
+    expand: Option<bool>,
+    block_id: Option<String>,
+}
+
+impl Section {
+    pub fn new(text: impl Into<Text>) -> Self {
+        Self {
+            ty: "section",
+            text: Some(text.into()),
+            fields: None,
+            accessory: None,
+            expand: None,
+            block_id: None,
+        }
+    }
+
+    /// A section of only fields, shown in two columns
+    pub fn fields(fields: Vec<Text>) -> Self {
+        Self {
+            ty: "section",
+            text: None,
+            fields: Some(fields),
+            accessory: None,
+            expand: None,
+            block_id: None,
+        }
+    }
+
+    /// Adds fields below the text
+    pub fn with_fields(mut self, fields: Vec<Text>) -> Self {
+        self.fields = Some(fields);
+        self
+    }
+
+    /// An element shown next to the text
+    pub fn accessory(mut self, element: impl Into<Element>) -> Self {
+        self.accessory = Some(element.into());
+        self
+    }
+
+    /// Always show all of the text, instead of a "see more" link for long text
+    pub fn expand(mut self) -> Self {
+        self.expand = Some(true);
+        self
+    }
+
+    fn validate(&self) -> Result<(), SlackError> {
+        check_block_id(&self.block_id)?;
+        match (&self.text, &self.fields) {
+            (None, None) => {
+                return Err(SlackError::InvalidInput(
+                    "a section needs text or fields".to_string(),
+                ))
+            }
+            (Some(text), _) => text.validate("section text", 3000)?,
+            _ => {}
+        }
+        if let Some(fields) = &self.fields {
+            check_count("fields in a section", fields.len(), 10)?;
+            fields
+                .iter()
+                .try_for_each(|field| field.validate("section field", 2000))?;
+        }
+        if let Some(accessory) = &self.accessory {
+            accessory.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// Something shown in a context block
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ContextElement {
+    Text(Text),
+    Image(ImageElement),
+}
+
+impl<T> From<T> for ContextElement
+where
+    T: Into<Text>,
+{
+    fn from(text: T) -> Self {
+        ContextElement::Text(text.into())
+    }
+}
+
+impl From<ImageElement> for ContextElement {
+    fn from(image: ImageElement) -> Self {
+        ContextElement::Image(image)
+    }
+}
+
+/// Small, grey text and images
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Context {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    elements: Vec<ContextElement>,
+    block_id: Option<String>,
+}
+
+impl Context {
+    pub fn new(elements: Vec<ContextElement>) -> Self {
+        Self {
+            ty: "context",
+            elements,
+            block_id: None,
+        }
+    }
+
+    fn validate(&self) -> Result<(), SlackError> {
+        check_block_id(&self.block_id)?;
+        check_count("elements in a context block", self.elements.len(), 10)?;
+        self.elements.iter().try_for_each(|element| match element {
+            ContextElement::Text(text) => text.validate("context text", 3000),
+            ContextElement::Image(image) => image.validate(),
+        })
+    }
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Divider {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    block_id: Option<String>,
+}
+
+impl Divider {
+    pub fn new() -> Self {
+        Self {
+            ty: "divider",
+            block_id: None,
+        }
+    }
+}
+
+impl Default for Divider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Big, bold text
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Header {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    text: PlainText,
+    block_id: Option<String>,
+}
+
+impl Header {
+    pub fn new(text: &str) -> Self {
+        Self {
+            ty: "header",
+            text: PlainText::new(text),
+            block_id: None,
+        }
+    }
+
+    fn validate(&self) -> Result<(), SlackError> {
+        check_block_id(&self.block_id)?;
+        self.text.validate("header text", 150)
+    }
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Image {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    image_url: String,
+    alt_text: String,
+    title: Option<PlainText>,
+    block_id: Option<String>,
+}
+
+impl Image {
+    pub fn new(image_url: &str, alt_text: &str) -> Self {
+        Self {
+            ty: "image",
+            image_url: image_url.to_string(),
+            alt_text: alt_text.to_string(),
+            title: None,
+            block_id: None,
+        }
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(PlainText::new(title));
+        self
+    }
+
+    fn validate(&self) -> Result<(), SlackError> {
+        check_block_id(&self.block_id)?;
+        check_length("image url", &self.image_url, 3000)?;
+        check_length("alt text", &self.alt_text, 2000)?;
+        if let Some(title) = &self.title {
+            title.validate("image title", 2000)?;
+        }
+        Ok(())
+    }
+}
+
+/// A row of interactive elements
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Actions {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    elements: Vec<Element>,
+    block_id: Option<String>,
+}
+
+impl Actions {
+    pub fn new(elements: Vec<Element>) -> Self {
+        Self {
+            ty: "actions",
+            elements,
+            block_id: None,
+        }
+    }
+
+    fn validate(&self) -> Result<(), SlackError> {
+        check_block_id(&self.block_id)?;
+        check_count("elements in an actions block", self.elements.len(), 25)?;
+        if self
+            .elements
+            .iter()
+            .any(|element| matches!(element, Element::Image(_)))
+        {
+            return Err(SlackError::InvalidInput(
+                "images can't be used in an actions block".to_string(),
+            ));
+        }
+        self.elements.iter().try_for_each(Element::validate)
+    }
+}
+
+/// A labelled input, whose value is sent when a modal is submitted
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Input {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    label: PlainText,
+    element: Element,
+    hint: Option<PlainText>,
+    optional: Option<bool>,
+    dispatch_action: Option<bool>,
+    block_id: Option<String>,
+}
+
+impl Input {
+    pub fn new(label: &str, element: impl Into<Element>) -> Self {
+        Self {
+            ty: "input",
+            label: PlainText::new(label),
+            element: element.into(),
+            hint: None,
+            optional: None,
+            dispatch_action: None,
+            block_id: None,
+        }
+    }
+
+    /// Shown below the input
+    pub fn hint(mut self, hint: &str) -> Self {
+        self.hint = Some(PlainText::new(hint));
+        self
+    }
+
+    /// Lets the view be submitted without filling this in
+    pub fn optional(mut self) -> Self {
+        self.optional = Some(true);
+        self
+    }
+
+    /// Sends a `block_actions` interaction when the value changes
+    pub fn dispatch_action(mut self) -> Self {
+        self.dispatch_action = Some(true);
+        self
+    }
+
+    fn validate(&self) -> Result<(), SlackError> {
+        check_block_id(&self.block_id)?;
+        self.label.validate("input label", 2000)?;
+        if let Some(hint) = &self.hint {
+            hint.validate("input hint", 2000)?;
+        }
+        if matches!(
+            self.element,
+            Element::Button(_) | Element::Overflow(_) | Element::Image(_)
+        ) {
+            return Err(SlackError::InvalidInput(
+                "buttons, overflow menus and images can't be used in an input block".to_string(),
+            ));
+        }
+        self.element.validate()
+    }
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct RichText {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    elements: Vec<RichTextElement>,
+    block_id: Option<String>,
+}
+
+impl RichText {
+    pub fn new(elements: Vec<RichTextElement>) -> Self {
+        Self {
+            ty: "rich_text",
+            elements,
+            block_id: None,
+        }
+    }
+}
+
+/// An embedded video, which needs the app to have `links.embed:write`
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Video {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    alt_text: String,
+    title: PlainText,
+    video_url: String,
+    thumbnail_url: String,
+    title_url: Option<String>,
+    description: Option<PlainText>,
+    author_name: Option<String>,
+    provider_name: Option<String>,
+    provider_icon_url: Option<String>,
+    block_id: Option<String>,
+}
+
+impl Video {
+    pub fn new(title: &str, video_url: &str, thumbnail_url: &str, alt_text: &str) -> Self {
+        Self {
+            ty: "video",
+            alt_text: alt_text.to_string(),
+            title: PlainText::new(title),
+            video_url: video_url.to_string(),
+            thumbnail_url: thumbnail_url.to_string(),
+            title_url: None,
+            description: None,
+            author_name: None,
+            provider_name: None,
+            provider_icon_url: None,
+            block_id: None,
+        }
+    }
+
+    pub fn title_url(mut self, url: &str) -> Self {
+        self.title_url = Some(url.to_string());
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(PlainText::new(description));
+        self
+    }
+
+    pub fn author_name(mut self, name: &str) -> Self {
+        self.author_name = Some(name.to_string());
+        self
+    }
+
+    pub fn provider(mut self, name: &str, icon_url: Option<&str>) -> Self {
+        self.provider_name = Some(name.to_string());
+        self.provider_icon_url = icon_url.map(str::to_string);
+        self
+    }
+
+    fn validate(&self) -> Result<(), SlackError> {
+        check_block_id(&self.block_id)?;
+        self.title.validate("video title", 199)?;
+        if let Some(author_name) = &self.author_name {
+            check_length("video author name", author_name, 49)?;
+        }
+        Ok(())
+    }
+}
+
+/// A remote file that was added with `files.remote.add`
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct File {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    external_id: String,
+    source: &'static str,
+    block_id: Option<String>,
+}
+
+impl File {
+    pub fn new(external_id: &str) -> Self {
+        Self {
+            ty: "file",
+            external_id: external_id.to_string(),
+            source: "remote",
+            block_id: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::Message;
+
+    fn dividers(count: usize) -> Vec<Block> {
+        (0..count).map(|_| Divider::new().into()).collect()
+    }
+
+    fn is_invalid(result: Result<(), SlackError>) -> bool {
+        matches!(result, Err(SlackError::InvalidInput(_)))
+    }
+
+    #[test]
+    fn block_counts_are_capped() {
+        let message = |count| Message::new().blocks(dividers(count)).build();
+        assert!(message(MAX_MESSAGE_BLOCKS).validate().is_ok());
+        assert!(is_invalid(message(MAX_MESSAGE_BLOCKS + 1).validate()));
+
+        assert!(validate_blocks(&dividers(MAX_VIEW_BLOCKS), MAX_VIEW_BLOCKS).is_ok());
+        assert!(is_invalid(validate_blocks(
+            &dividers(MAX_VIEW_BLOCKS + 1),
+            MAX_VIEW_BLOCKS
+        )));
+    }
+
+    #[test]
+    fn text_lengths_are_checked_in_characters() {
+        // multibyte characters count once
+        assert!(Block::from(Header::new(&"é".repeat(150)))
+            .validate()
+            .is_ok());
+        assert!(is_invalid(
+            Block::from(Header::new(&"é".repeat(151))).validate()
+        ));
+
+        assert!(Block::from(Section::new("a".repeat(3000)))
+            .validate()
+            .is_ok());
+        assert!(is_invalid(
+            Block::from(Section::new("a".repeat(3001))).validate()
+        ));
+
+        let button = |text: &str| Block::from(Actions::new(vec![Button::new(text, "b").into()]));
+        assert!(button(&"a".repeat(75)).validate().is_ok());
+        assert!(is_invalid(button(&"a".repeat(76)).validate()));
+
+        assert!(is_invalid(
+            Block::from(Divider::new().block_id(&"a".repeat(256))).validate()
+        ));
+    }
+
+    #[test]
+    fn element_counts_are_capped() {
+        let actions = |count| {
+            Block::from(Actions::new(
+                (0..count)
+                    .map(|i| Button::new("press", &i.to_string()).into())
+                    .collect(),
+            ))
+        };
+        assert!(actions(25).validate().is_ok());
+        assert!(is_invalid(actions(26).validate()));
+
+        let fields = |count| Block::from(Section::fields(vec![Text::plain("field"); count]));
+        assert!(fields(10).validate().is_ok());
+        assert!(is_invalid(fields(11).validate()));
+
+        let select = |count| {
+            let options = vec![SelectOption::new("option", "value"); count];
+            Block::from(Actions::new(
+                vec![StaticSelect::new("pick", options).into()],
+            ))
+        };
+        assert!(select(100).validate().is_ok());
+        assert!(is_invalid(select(101).validate()));
+    }
+}
@@ -0,0 +1,145 @@
+use serde::Serialize;
+
+use crate::client::{Channel, User};
+
+/// Formatted text, built from sections, lists, code blocks and quotes
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RichTextElement {
+    RichTextSection {
+        elements: Vec<Inline>,
+    },
+    RichTextList {
+        style: ListStyle,
+        elements: Vec<RichTextElement>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        indent: Option<u32>,
+    },
+    RichTextPreformatted {
+        elements: Vec<Inline>,
+    },
+    RichTextQuote {
+        elements: Vec<Inline>,
+    },
+}
+
+impl RichTextElement {
+    pub fn section(elements: Vec<Inline>) -> Self {
+        RichTextElement::RichTextSection { elements }
+    }
+
+    /// A list of sections
+    pub fn list(style: ListStyle, items: Vec<Vec<Inline>>) -> Self {
+        RichTextElement::RichTextList {
+            style,
+            elements: items.into_iter().map(RichTextElement::section).collect(),
+            indent: None,
+        }
+    }
+
+    /// A code block
+    pub fn preformatted(elements: Vec<Inline>) -> Self {
+        RichTextElement::RichTextPreformatted { elements }
+    }
+
+    pub fn quote(elements: Vec<Inline>) -> Self {
+        RichTextElement::RichTextQuote { elements }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListStyle {
+    Bullet,
+    Ordered,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TextStyle {
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub bold: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub italic: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub strike: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub code: bool,
+}
+
+impl TextStyle {
+    fn is_plain(&self) -> bool {
+        *self == TextStyle::default()
+    }
+}
+
+/// A piece of a rich text section
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Inline {
+    Text {
+        text: String,
+        #[serde(skip_serializing_if = "TextStyle::is_plain")]
+        style: TextStyle,
+    },
+    Link {
+        url: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+    },
+    Emoji {
+        name: String,
+    },
+    User {
+        user_id: User,
+    },
+    Channel {
+        channel_id: Channel,
+    },
+    Usergroup {
+        usergroup_id: String,
+    },
+    /// Mentions `@here`, `@channel` or `@everyone`
+    Broadcast {
+        range: String,
+    },
+}
+
+impl Inline {
+    pub fn text(text: &str) -> Self {
+        Inline::Text {
+            text: text.to_string(),
+            style: TextStyle::default(),
+        }
+    }
+
+    pub fn styled(text: &str, style: TextStyle) -> Self {
+        Inline::Text {
+            text: text.to_string(),
+            style,
+        }
+    }
+
+    pub fn link(url: &str, text: Option<&str>) -> Self {
+        Inline::Link {
+            url: url.to_string(),
+            text: text.map(str::to_string),
+        }
+    }
+
+    /// An emoji by its name, without the colons
+    pub fn emoji(name: &str) -> Self {
+        Inline::Emoji {
+            name: name.to_string(),
+        }
+    }
+
+    pub fn user(user: User) -> Self {
+        Inline::User { user_id: user }
+    }
+
+    pub fn channel(channel: Channel) -> Self {
+        Inline::Channel {
+            channel_id: channel,
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use serde::Serialize;
+
+use super::{check_count, check_length};
+use crate::client::SlackError;
+
+/// Text that's shown as is, which is all some fields accept
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct PlainText {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    pub(crate) text: String,
+    emoji: Option<bool>,
+}
+
+impl PlainText {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            ty: "plain_text",
+            text: text.into(),
+            emoji: Some(true),
+        }
+    }
+
+    /// Show emoji as their `:name:` instead of the emoji itself
+    pub fn no_emoji(mut self) -> Self {
+        self.emoji = Some(false);
+        self
+    }
+
+    pub(crate) fn validate(&self, what: &str, max: usize) -> Result<(), SlackError> {
+        check_length(what, &self.text, max)
+    }
+}
+
+impl From<&str> for PlainText {
+    fn from(text: &str) -> Self {
+        PlainText::new(text)
+    }
+}
+
+impl From<String> for PlainText {
+    fn from(text: String) -> Self {
+        PlainText::new(text)
+    }
+}
+
+/// Plain or formatted text
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Text {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    text: String,
+    emoji: Option<bool>,
+    verbatim: Option<bool>,
+}
+
+impl Text {
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self {
+            ty: "plain_text",
+            text: text.into(),
+            emoji: Some(true),
+            verbatim: None,
+        }
+    }
+
+    /// Text using Slack's markdown, like `*bold*` and `<@U123>`
+    pub fn mrkdwn(text: impl Into<String>) -> Self {
+        Self {
+            ty: "mrkdwn",
+            text: text.into(),
+            emoji: None,
+            verbatim: None,
+        }
+    }
+
+    /// Don't turn URLs, channel names and mentions into links by themselves
+    pub fn verbatim(mut self) -> Self {
+        if self.ty == "mrkdwn" {
+            self.verbatim = Some(true);
+        }
+        self
+    }
+
+    pub(crate) fn validate(&self, what: &str, max: usize) -> Result<(), SlackError> {
+        check_length(what, &self.text, max)
+    }
+}
+
+/// Formatted text
+impl From<&str> for Text {
+    fn from(text: &str) -> Self {
+        Text::mrkdwn(text)
+    }
+}
+
+/// Formatted text
+impl From<String> for Text {
+    fn from(text: String) -> Self {
+        Text::mrkdwn(text)
+    }
+}
+
+impl From<PlainText> for Text {
+    fn from(text: PlainText) -> Self {
+        Self {
+            ty: text.ty,
+            text: text.text,
+            emoji: text.emoji,
+            verbatim: None,
+        }
+    }
+}
+
+/// An option in a select menu, overflow menu, checkboxes or radio buttons
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct SelectOption {
+    text: Text,
+    value: String,
+    description: Option<Text>,
+    url: Option<String>,
+}
+
+impl SelectOption {
+    pub fn new(text: &str, value: &str) -> Self {
+        Self {
+            text: Text::plain(text),
+            value: value.to_string(),
+            description: None,
+            url: None,
+        }
+    }
+
+    /// An option with formatted text, which only checkboxes and radio buttons can show
+    pub fn mrkdwn(text: &str, value: &str) -> Self {
+        Self {
+            text: Text::mrkdwn(text),
+            ..Self::new(text, value)
+        }
+    }
+
+    /// Shown below the text of the option
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(Text::plain(description));
+        self
+    }
+
+    /// Opens the URL when the option is picked. Only works in overflow menus.
+    pub fn url(mut self, url: &str) -> Self {
+        self.url = Some(url.to_string());
+        self
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), SlackError> {
+        self.text.validate("option text", 75)?;
+        check_length("option value", &self.value, 150)?;
+        if let Some(description) = &self.description {
+            description.validate("option description", 75)?;
+        }
+        if let Some(url) = &self.url {
+            check_length("option url", url, 3000)?;
+        }
+        Ok(())
+    }
+}
+
+/// A labelled group of options
+#[derive(Debug, Clone, Serialize)]
+pub struct OptionGroup {
+    label: PlainText,
+    options: Vec<SelectOption>,
+}
+
+impl OptionGroup {
+    pub fn new(label: &str, options: Vec<SelectOption>) -> Self {
+        Self {
+            label: PlainText::new(label),
+            options,
+        }
+    }
+
+    /// Drops the options after the first `max`
+    pub(crate) fn truncate(&mut self, max: usize) {
+        self.options.truncate(max);
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), SlackError> {
+        self.label.validate("option group label", 75)?;
+        check_count("options in a group", self.options.len(), 100)?;
+        self.options.iter().try_for_each(SelectOption::validate)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Style {
+    /// Green
+    Primary,
+    /// Red
+    Danger,
+}
+
+/// Asks the user to confirm before an element does anything
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct Confirm {
+    title: PlainText,
+    text: Text,
+    confirm: PlainText,
+    deny: PlainText,
+    style: Option<Style>,
+}
+
+impl Confirm {
+    pub fn new(title: &str, text: impl Into<Text>) -> Self {
+        Self {
+            title: PlainText::new(title),
+            text: text.into(),
+            confirm: PlainText::new("Yes"),
+            deny: PlainText::new("Cancel"),
+            style: None,
+        }
+    }
+
+    /// The label of the confirm button
+    pub fn confirm(mut self, confirm: &str) -> Self {
+        self.confirm = PlainText::new(confirm);
+        self
+    }
+
+    /// The label of the button that cancels
+    pub fn deny(mut self, deny: &str) -> Self {
+        self.deny = PlainText::new(deny);
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), SlackError> {
+        self.title.validate("confirm title", 100)?;
+        self.text.validate("confirm text", 300)?;
+        self.confirm.validate("confirm button", 30)?;
+        self.deny.validate("deny button", 30)
+    }
+}
@@ -0,0 +1,21 @@
+use crate::client::{api::auth, ContextSupplier, SlackError, User};
+
+/// The identity of the bot crow is running as, as reported by `auth.test`
+#[derive(Debug, Clone)]
+pub struct Bot {
+    pub user: User,
+    pub bot_id: String,
+    pub team_id: String,
+}
+
+impl Bot {
+    pub(crate) async fn fetch(ctx: &impl ContextSupplier) -> Result<Self, SlackError> {
+        let response = ctx.get_context().api.call(&auth::Test {}).await?;
+
+        Ok(Self {
+            user: response.user_id,
+            bot_id: response.bot_id.unwrap_or_default(),
+            team_id: response.team_id,
+        })
+    }
+}
@@ -1,9 +1,14 @@
-use std::{default, str::FromStr};
-
-use serde::{Serialize, Serializer};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::client::{
-    model::user::User, Context, ContextSupplier, Message, SlackApiResponse, SlackError,
+    api::{chat, conversations, Paginate},
+    model::{
+        blocks::check_count,
+        message::{PostedMessage, ScheduledMessage},
+        user::User,
+    },
+    ContextSupplier, Message, SlackError,
 };
 
 #[derive(Default, Debug, Clone)]
@@ -20,53 +25,415 @@ impl Serialize for Channel {
     }
 }
 
+impl<'de> Deserialize<'de> for Channel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(Self::new)
+    }
+}
+
 impl Channel {
+    /// Posts the message in this channel, or only for one user if `ephemeral` is set.
+    /// Ephemeral messages can't be replied to, updated or deleted through the handle.
     pub async fn post_message(
         &self,
-        ctx: &impl ContextSupplier<'_>,
+        ctx: &impl ContextSupplier,
         mut msg: Message,
         ephemeral: Option<User>,
-    ) -> Result<(), SlackError> {
+    ) -> Result<PostedMessage, SlackError> {
         msg.channel = Some(Channel {
             id: self.id.clone(),
         });
 
-        println!("{}", serde_json::to_string_pretty(&msg).unwrap());
+        let api = &ctx.get_context().api;
+
+        if let Some(user) = ephemeral {
+            let response = api
+                .call(&chat::PostEphemeral { message: msg, user })
+                .await?;
 
-        let is_ephemeral = ephemeral.is_some();
-        if is_ephemeral {
-            msg.receiver = ephemeral;
+            return Ok(PostedMessage::new(self.clone(), response.message_ts));
         }
 
-        let response: SlackApiResponse = ctx
+        let response = api.call(&chat::PostMessage { message: msg }).await?;
+
+        Ok(PostedMessage::posted(response))
+    }
+
+    /// Has Slack post the message at `post_at`, which can be up to 120 days ahead
+    pub async fn schedule_message(
+        &self,
+        ctx: &impl ContextSupplier,
+        mut msg: Message,
+        post_at: DateTime<Utc>,
+    ) -> Result<ScheduledMessage, SlackError> {
+        msg.channel = Some(self.clone());
+
+        let response = ctx
             .get_context()
-            .client
-            .post(if is_ephemeral {
-                "https://slack.com/api/chat.postEphemeral"
-            } else {
-                "https://slack.com/api/chat.postMessage"
+            .api
+            .call(&chat::ScheduleMessage {
+                message: msg,
+                post_at: post_at.timestamp(),
             })
-            // TODO: make into struct
-            .json(&msg)
-            .header(
-                "Authorization",
-                &("Bearer ".to_string() + &ctx.get_context().token),
-            )
-            .send()
-            .await
-            .unwrap()
-            .json()
-            .await
-            .unwrap();
-
-        if !response.ok {
-            return Err(SlackError::ApiError(response.error.unwrap()));
+            .await?;
+
+        Ok(ScheduledMessage {
+            channel: response.channel,
+            id: response.scheduled_message_id,
+            post_at: response.post_at,
+        })
+    }
+
+    /// The messages the bot has scheduled in this channel that haven't been posted yet
+    pub fn scheduled_messages(
+        &self,
+        ctx: &impl ContextSupplier,
+    ) -> Paginate<chat::ScheduledMessagesList> {
+        ctx.get_context().api.paginate(chat::ScheduledMessagesList {
+            channel: Some(self.clone()),
+            ..Default::default()
+        })
+    }
+
+    /// Posts the text in italics, like the `/me` command does
+    pub async fn me_message(
+        &self,
+        ctx: &impl ContextSupplier,
+        text: &str,
+    ) -> Result<PostedMessage, SlackError> {
+        let response = ctx
+            .get_context()
+            .api
+            .call(&chat::MeMessage {
+                channel: self.clone(),
+                text: text.to_string(),
+            })
+            .await?;
+        Ok(PostedMessage::new(response.channel, response.ts))
+    }
+
+    /// Looks up the name, topic, member count and more of the channel
+    pub async fn info(&self, ctx: &impl ContextSupplier) -> Result<ChannelInfo, SlackError> {
+        let response = ctx
+            .get_context()
+            .api
+            .call(&conversations::Info {
+                channel: self.clone(),
+                include_num_members: Some(true),
+            })
+            .await?;
+        Ok(response.channel)
+    }
+
+    /// The messages in the channel between `oldest` and `latest`, newest first.
+    /// Replies in threads aren't included, those are listed with `replies`.
+    pub fn history(
+        &self,
+        ctx: &impl ContextSupplier,
+        oldest: Option<DateTime<Utc>>,
+        latest: Option<DateTime<Utc>>,
+    ) -> Paginate<conversations::History> {
+        ctx.get_context().api.paginate(conversations::History {
+            channel: self.clone(),
+            oldest: oldest.map(to_ts),
+            latest: latest.map(to_ts),
+            inclusive: None,
+            cursor: None,
+            limit: None,
+        })
+    }
+
+    /// The thread on the message with `thread_ts`, starting with that message
+    pub fn replies(
+        &self,
+        ctx: &impl ContextSupplier,
+        thread_ts: &str,
+        oldest: Option<DateTime<Utc>>,
+        latest: Option<DateTime<Utc>>,
+    ) -> Paginate<conversations::Replies> {
+        ctx.get_context().api.paginate(conversations::Replies {
+            channel: self.clone(),
+            ts: thread_ts.to_string(),
+            oldest: oldest.map(to_ts),
+            latest: latest.map(to_ts),
+            inclusive: None,
+            cursor: None,
+            limit: None,
+        })
+    }
+
+    /// Everyone in the channel
+    pub fn members(&self, ctx: &impl ContextSupplier) -> Paginate<conversations::Members> {
+        ctx.get_context().api.paginate(conversations::Members {
+            channel: self.clone(),
+            cursor: None,
+            limit: None,
+        })
+    }
+
+    /// Every conversation of the given types the bot can see, or only public channels if no
+    /// types are given
+    pub fn list(
+        ctx: &impl ContextSupplier,
+        types: &[ConversationType],
+    ) -> Paginate<conversations::List> {
+        let types = types
+            .iter()
+            .map(|ty| ty.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        ctx.get_context().api.paginate(conversations::List {
+            types: (!types.is_empty()).then_some(types),
+            ..Default::default()
+        })
+    }
+
+    /// Adds the bot to the channel, which only works for public channels
+    pub async fn join(&self, ctx: &impl ContextSupplier) -> Result<ChannelInfo, SlackError> {
+        let response = ctx
+            .get_context()
+            .api
+            .call(&conversations::Join {
+                channel: self.clone(),
+            })
+            .await?;
+        Ok(response.channel)
+    }
+
+    pub async fn leave(&self, ctx: &impl ContextSupplier) -> Result<(), SlackError> {
+        ctx.get_context()
+            .api
+            .call(&conversations::Leave {
+                channel: self.clone(),
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Adds up to 1000 users to the channel
+    pub async fn invite(
+        &self,
+        ctx: &impl ContextSupplier,
+        users: &[User],
+    ) -> Result<ChannelInfo, SlackError> {
+        if users.is_empty() {
+            return Err(SlackError::InvalidInput(
+                "at least one user needs to be invited".to_string(),
+            ));
         }
+        check_count("users to invite", users.len(), 1000)?;
+
+        let users = users
+            .iter()
+            .map(|user| user.id.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
 
+        let response = ctx
+            .get_context()
+            .api
+            .call(&conversations::Invite {
+                channel: self.clone(),
+                users,
+            })
+            .await?;
+        Ok(response.channel)
+    }
+
+    /// Removes a user from the channel
+    pub async fn kick(&self, ctx: &impl ContextSupplier, user: &User) -> Result<(), SlackError> {
+        ctx.get_context()
+            .api
+            .call(&conversations::Kick {
+                channel: self.clone(),
+                user: user.clone(),
+            })
+            .await?;
         Ok(())
     }
 
-    pub(crate) fn new(id: String) -> Self {
+    pub async fn set_topic(
+        &self,
+        ctx: &impl ContextSupplier,
+        topic: &str,
+    ) -> Result<ChannelInfo, SlackError> {
+        let response = ctx
+            .get_context()
+            .api
+            .call(&conversations::SetTopic {
+                channel: self.clone(),
+                topic: topic.to_string(),
+            })
+            .await?;
+        Ok(response.channel)
+    }
+
+    pub async fn set_purpose(
+        &self,
+        ctx: &impl ContextSupplier,
+        purpose: &str,
+    ) -> Result<ChannelInfo, SlackError> {
+        let response = ctx
+            .get_context()
+            .api
+            .call(&conversations::SetPurpose {
+                channel: self.clone(),
+                purpose: purpose.to_string(),
+            })
+            .await?;
+        Ok(response.channel)
+    }
+
+    pub async fn rename(
+        &self,
+        ctx: &impl ContextSupplier,
+        name: &str,
+    ) -> Result<ChannelInfo, SlackError> {
+        let response = ctx
+            .get_context()
+            .api
+            .call(&conversations::Rename {
+                channel: self.clone(),
+                name: name.to_string(),
+            })
+            .await?;
+        Ok(response.channel)
+    }
+
+    pub async fn archive(&self, ctx: &impl ContextSupplier) -> Result<(), SlackError> {
+        ctx.get_context()
+            .api
+            .call(&conversations::Archive {
+                channel: self.clone(),
+            })
+            .await?;
+        Ok(())
+    }
+
+    pub async fn unarchive(&self, ctx: &impl ContextSupplier) -> Result<(), SlackError> {
+        ctx.get_context()
+            .api
+            .call(&conversations::Unarchive {
+                channel: self.clone(),
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Creates a channel. Names can only have lowercase letters, numbers, hyphens and
+    /// underscores.
+    pub async fn create(
+        ctx: &impl ContextSupplier,
+        name: &str,
+        is_private: bool,
+    ) -> Result<ChannelInfo, SlackError> {
+        let response = ctx
+            .get_context()
+            .api
+            .call(&conversations::Create {
+                name: name.to_string(),
+                is_private: Some(is_private),
+                team_id: None,
+            })
+            .await?;
+        Ok(response.channel)
+    }
+
+    pub fn new(id: String) -> Self {
         Self { id }
     }
 }
+
+/// Turns a time into the `ts` format Slack uses for bounds
+fn to_ts(time: DateTime<Utc>) -> String {
+    format!("{}.{:06}", time.timestamp(), time.timestamp_subsec_micros())
+}
+
+/// A conversation as Slack describes it
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChannelInfo {
+    pub id: Channel,
+    /// Not set for direct messages
+    pub name: Option<String>,
+    #[serde(default)]
+    pub is_channel: bool,
+    #[serde(default)]
+    pub is_group: bool,
+    #[serde(default)]
+    pub is_im: bool,
+    #[serde(default)]
+    pub is_mpim: bool,
+    #[serde(default)]
+    pub is_private: bool,
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Whether the bot is in the channel
+    #[serde(default)]
+    pub is_member: bool,
+    /// When the channel was created, as a UNIX timestamp
+    #[serde(default)]
+    pub created: i64,
+    pub topic: Option<Topic>,
+    pub purpose: Option<Topic>,
+    /// Only set by `Channel::info` and the methods that list conversations
+    pub num_members: Option<u32>,
+    /// The other user, for direct messages
+    pub user: Option<User>,
+}
+
+impl ChannelInfo {
+    pub fn created_at(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp(self.created, 0).unwrap_or_default()
+    }
+}
+
+/// The topic or purpose of a channel
+#[derive(Debug, Clone, Deserialize)]
+pub struct Topic {
+    pub value: String,
+    pub creator: User,
+    /// When it was set, as a UNIX timestamp
+    pub last_set: i64,
+}
+
+/// The kinds of conversations that can be listed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversationType {
+    PublicChannel,
+    PrivateChannel,
+    /// A group direct message
+    Mpim,
+    /// A direct message
+    Im,
+}
+
+impl ConversationType {
+    fn as_str(self) -> &'static str {
+        match self {
+            ConversationType::PublicChannel => "public_channel",
+            ConversationType::PrivateChannel => "private_channel",
+            ConversationType::Mpim => "mpim",
+            ConversationType::Im => "im",
+        }
+    }
+}
+
+/// The kind of conversation an event happened in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelType {
+    /// A public channel
+    Channel,
+    /// A private channel
+    Group,
+    /// A direct message
+    Im,
+    /// A group direct message
+    Mpim,
+    /// The App Home of the bot
+    AppHome,
+}
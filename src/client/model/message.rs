@@ -1,88 +1,493 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use super::{channel::Channel, user::User};
+use super::{
+    blocks::{check_count, check_length, validate_blocks, Block, MAX_MESSAGE_BLOCKS},
+    channel::Channel,
+    user::User,
+};
+use crate::client::{
+    api::{chat, pins, reactions},
+    ContextSupplier, SlackError,
+};
 
-// TODO: add more stuff to this
-// TODO: make pub(super) stuff more public? theres no reason for it not to be
-// TODO: make a macro for the #[serde(skip_serializing_if = "Option::is_none")] bits
+/// How long the text of a message can be before Slack cuts it off
+const MAX_TEXT_LENGTH: usize = 40000;
+
+/// A message, with everything `chat.postMessage` accepts
 #[serde_with::skip_serializing_none]
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct Message {
     pub channel: Option<Channel>,
-    #[serde(flatten)]
-    pub content: MessageContent,
-
-    // non user stuff, but needed for json
-    thread_ts: Option<String>,
-    #[serde(rename = "user")]
-    pub(super) receiver: Option<User>,
+    /// The message itself, or the fallback used in notifications if there are blocks
+    pub text: Option<String>,
+    pub blocks: Option<Vec<Block>>,
+    pub attachments: Option<Vec<Attachment>>,
+    /// The `ts` of the message this is a reply to
+    pub thread_ts: Option<String>,
+    /// Also post a thread reply in the channel
+    pub reply_broadcast: Option<bool>,
+    pub unfurl_links: Option<bool>,
+    pub unfurl_media: Option<bool>,
+    /// Whether the text is formatted with Slack's markdown. Defaults to true.
+    pub mrkdwn: Option<bool>,
+    pub icon_emoji: Option<String>,
+    pub icon_url: Option<String>,
+    pub username: Option<String>,
+    pub metadata: Option<MessageMetadata>,
 }
 
 impl Message {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new() -> MessageBuilder {
         MessageBuilder {
-            channel: None,
-            content: None,
+            message: Message::default(),
         }
     }
-}
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "lowercase")]
-pub enum MessageContent {
-    Text(String),
-    Blocks(String),
-}
-
-impl Default for MessageContent {
-    fn default() -> Self {
-        Self::Text(String::new())
+    /// Checks the message against Slack's limits, which happens before it's sent
+    pub fn validate(&self) -> Result<(), SlackError> {
+        if self.text.is_none() && self.blocks.is_none() && self.attachments.is_none() {
+            return Err(SlackError::InvalidInput(
+                "a message needs text, blocks or attachments".to_string(),
+            ));
+        }
+        if let Some(text) = &self.text {
+            check_length("message text", text, MAX_TEXT_LENGTH)?;
+        }
+        if let Some(blocks) = &self.blocks {
+            validate_blocks(blocks, MAX_MESSAGE_BLOCKS)?;
+        }
+        if let Some(attachments) = &self.attachments {
+            check_count("attachments", attachments.len(), 100)?;
+            for attachment in attachments {
+                if let Some(blocks) = &attachment.blocks {
+                    validate_blocks(blocks, MAX_MESSAGE_BLOCKS)?;
+                }
+            }
+        }
+        Ok(())
     }
 }
 
 impl From<&str> for Message {
     fn from(text: &str) -> Self {
         Message {
-            channel: None,
-            content: MessageContent::Text(text.to_string()),
+            text: Some(text.to_string()),
+            ..Message::default()
+        }
+    }
+}
+
+impl From<String> for Message {
+    fn from(text: String) -> Self {
+        Message {
+            text: Some(text),
+            ..Message::default()
+        }
+    }
+}
+
+/// A message that was posted, which can be replied to, edited or deleted
+#[derive(Debug, Clone)]
+pub struct PostedMessage {
+    pub channel: Channel,
+    pub ts: String,
+    /// The `ts` of the thread the message is in, if it's a reply
+    pub thread_ts: Option<String>,
+}
+
+impl PostedMessage {
+    pub fn new(channel: Channel, ts: String) -> Self {
+        Self {
+            channel,
+            ts,
             thread_ts: None,
-            receiver: None,
+        }
+    }
+
+    pub(crate) fn posted(response: chat::PostMessageResponse) -> Self {
+        Self {
+            channel: response.channel,
+            ts: response.ts,
+            thread_ts: response.message.and_then(|message| message.thread_ts),
+        }
+    }
+
+    /// Replies in the thread of this message
+    pub async fn reply(
+        &self,
+        ctx: &impl ContextSupplier,
+        mut msg: Message,
+    ) -> Result<PostedMessage, SlackError> {
+        msg.channel = Some(self.channel.clone());
+        msg.thread_ts = Some(self.thread_ts.clone().unwrap_or_else(|| self.ts.clone()));
+
+        let response = ctx
+            .get_context()
+            .api
+            .call(&chat::PostMessage { message: msg })
+            .await?;
+        Ok(PostedMessage::posted(response))
+    }
+
+    /// Replaces the content of this message
+    pub async fn update(
+        &self,
+        ctx: &impl ContextSupplier,
+        mut msg: Message,
+    ) -> Result<(), SlackError> {
+        msg.channel = Some(self.channel.clone());
+
+        ctx.get_context()
+            .api
+            .call(&chat::Update {
+                ts: self.ts.clone(),
+                message: msg,
+            })
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete(&self, ctx: &impl ContextSupplier) -> Result<(), SlackError> {
+        ctx.get_context()
+            .api
+            .call(&chat::Delete {
+                channel: self.channel.clone(),
+                ts: self.ts.clone(),
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Adds a reaction, by the name of the emoji without colons
+    pub async fn react(&self, ctx: &impl ContextSupplier, emoji: &str) -> Result<(), SlackError> {
+        ctx.get_context()
+            .api
+            .call(&reactions::Add {
+                channel: self.channel.clone(),
+                timestamp: self.ts.clone(),
+                name: emoji.to_string(),
+            })
+            .await?;
+        Ok(())
+    }
+
+    pub async fn pin(&self, ctx: &impl ContextSupplier) -> Result<(), SlackError> {
+        ctx.get_context()
+            .api
+            .call(&pins::Add {
+                channel: self.channel.clone(),
+                timestamp: self.ts.clone(),
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// A link to this message
+    pub async fn get_permalink(&self, ctx: &impl ContextSupplier) -> Result<String, SlackError> {
+        let response = ctx
+            .get_context()
+            .api
+            .call(&chat::GetPermalink {
+                channel: self.channel.clone(),
+                message_ts: self.ts.clone(),
+            })
+            .await?;
+        Ok(response.permalink)
+    }
+}
+
+/// A message Slack will post later, which can be deleted until then
+#[derive(Debug, Clone)]
+pub struct ScheduledMessage {
+    pub channel: Channel,
+    pub id: String,
+    /// When it will be posted, as a UNIX timestamp
+    pub post_at: i64,
+}
+
+impl ScheduledMessage {
+    /// Stops the message from being posted
+    pub async fn delete(&self, ctx: &impl ContextSupplier) -> Result<(), SlackError> {
+        ctx.get_context()
+            .api
+            .call(&chat::DeleteScheduledMessage {
+                channel: self.channel.clone(),
+                scheduled_message_id: self.id.clone(),
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+/// A message as it's stored in a conversation
+#[derive(Debug, Clone, Deserialize)]
+pub struct HistoryMessage {
+    pub ts: String,
+    /// The author, which is missing for some bot messages
+    pub user: Option<User>,
+    pub bot_id: Option<String>,
+    #[serde(default)]
+    pub text: String,
+    pub subtype: Option<String>,
+    pub thread_ts: Option<String>,
+    /// How many replies there are, if a thread was started on the message
+    pub reply_count: Option<u32>,
+}
+
+impl HistoryMessage {
+    /// A handle for replying to, editing or deleting the message
+    pub fn posted(&self, channel: &Channel) -> PostedMessage {
+        PostedMessage {
+            channel: channel.clone(),
+            ts: self.ts.clone(),
+            thread_ts: self.thread_ts.clone(),
         }
     }
 }
 
 pub struct MessageBuilder {
-    pub channel: Option<String>,
-    pub content: Option<MessageContent>,
+    message: Message,
 }
 
 impl MessageBuilder {
     pub fn channel_id(mut self, channel: String) -> Self {
-        self.channel = Some(channel);
+        self.message.channel = Some(Channel { id: channel });
         self
     }
 
     pub fn channel(mut self, channel: Channel) -> Self {
-        self.channel = Some(channel.id);
+        self.message.channel = Some(channel);
         self
     }
 
+    /// The text of the message. If there are blocks too, this is only shown in notifications.
     pub fn text(mut self, text: String) -> Self {
-        self.content = Some(MessageContent::Text(text));
+        self.message.text = Some(text);
+        self
+    }
+
+    pub fn blocks(mut self, blocks: Vec<Block>) -> Self {
+        self.message.blocks = Some(blocks);
+        self
+    }
+
+    pub fn attachment(mut self, attachment: Attachment) -> Self {
+        self.message
+            .attachments
+            .get_or_insert_with(Vec::new)
+            .push(attachment);
+        self
+    }
+
+    /// Posts the message as a reply in the thread of the message with this `ts`
+    pub fn thread_ts(mut self, thread_ts: &str) -> Self {
+        self.message.thread_ts = Some(thread_ts.to_string());
+        self
+    }
+
+    /// Also shows the thread reply in the channel
+    pub fn reply_broadcast(mut self) -> Self {
+        self.message.reply_broadcast = Some(true);
+        self
+    }
+
+    /// Whether links to text-based content are previewed
+    pub fn unfurl_links(mut self, unfurl: bool) -> Self {
+        self.message.unfurl_links = Some(unfurl);
+        self
+    }
+
+    /// Whether links to images, videos and other media are previewed
+    pub fn unfurl_media(mut self, unfurl: bool) -> Self {
+        self.message.unfurl_media = Some(unfurl);
+        self
+    }
+
+    /// Whether the text is formatted with Slack's markdown
+    pub fn mrkdwn(mut self, mrkdwn: bool) -> Self {
+        self.message.mrkdwn = Some(mrkdwn);
+        self
+    }
+
+    /// Posts as a different name. Needs the `chat:write.customize` scope.
+    pub fn username(mut self, username: &str) -> Self {
+        self.message.username = Some(username.to_string());
         self
     }
 
-    pub fn blocks(mut self, blocks: String) -> Self {
-        self.content = Some(MessageContent::Blocks(blocks));
+    /// Uses an emoji, like `:bird:`, as the picture. Needs the `chat:write.customize` scope.
+    pub fn icon_emoji(mut self, emoji: &str) -> Self {
+        self.message.icon_emoji = Some(emoji.to_string());
         self
     }
 
+    /// Uses an image as the picture. Needs the `chat:write.customize` scope.
+    pub fn icon_url(mut self, url: &str) -> Self {
+        self.message.icon_url = Some(url.to_string());
+        self
+    }
+
+    /// Attaches data for other apps to read, which users don't see.
+    /// Fails if the payload can't be turned into JSON, like a map with keys that aren't strings.
+    pub fn metadata(
+        mut self,
+        event_type: &str,
+        payload: impl Serialize,
+    ) -> Result<Self, SlackError> {
+        let event_payload = serde_json::to_value(payload).map_err(|err| {
+            SlackError::InvalidInput(format!("message metadata can't be serialized: {}", err))
+        })?;
+
+        self.message.metadata = Some(MessageMetadata {
+            event_type: event_type.to_string(),
+            event_payload,
+        });
+        Ok(self)
+    }
+
     pub fn build(self) -> Message {
-        Message {
-            channel: self.channel.map(|id| Channel { id }),
-            content: self.content.unwrap(),
-            thread_ts: None,
-            receiver: None,
-        }
+        self.message
+    }
+}
+
+/// Data attached to a message for apps to read
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageMetadata {
+    pub event_type: String,
+    pub event_payload: Value,
+}
+
+/// A legacy attachment, shown below the message with a colored bar on its side
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Attachment {
+    color: Option<String>,
+    fallback: Option<String>,
+    pretext: Option<String>,
+    author_name: Option<String>,
+    author_link: Option<String>,
+    author_icon: Option<String>,
+    title: Option<String>,
+    title_link: Option<String>,
+    text: Option<String>,
+    fields: Option<Vec<AttachmentField>>,
+    image_url: Option<String>,
+    thumb_url: Option<String>,
+    footer: Option<String>,
+    footer_icon: Option<String>,
+    ts: Option<i64>,
+    blocks: Option<Vec<Block>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct AttachmentField {
+    title: String,
+    value: String,
+    short: bool,
+}
+
+impl Attachment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The color of the bar, as a hex code like `#36a64f` or one of `good`, `warning` and
+    /// `danger`
+    pub fn color(mut self, color: &str) -> Self {
+        self.color = Some(color.to_string());
+        self
+    }
+
+    /// Shown where the attachment can't be, like in notifications
+    pub fn fallback(mut self, fallback: &str) -> Self {
+        self.fallback = Some(fallback.to_string());
+        self
+    }
+
+    /// Shown above the attachment
+    pub fn pretext(mut self, pretext: &str) -> Self {
+        self.pretext = Some(pretext.to_string());
+        self
+    }
+
+    pub fn author(mut self, name: &str, link: Option<&str>, icon: Option<&str>) -> Self {
+        self.author_name = Some(name.to_string());
+        self.author_link = link.map(str::to_string);
+        self.author_icon = icon.map(str::to_string);
+        self
+    }
+
+    pub fn title(mut self, title: &str, link: Option<&str>) -> Self {
+        self.title = Some(title.to_string());
+        self.title_link = link.map(str::to_string);
+        self
+    }
+
+    pub fn text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    /// Adds a field. Short fields are shown next to each other.
+    pub fn field(mut self, title: &str, value: &str, short: bool) -> Self {
+        self.fields
+            .get_or_insert_with(Vec::new)
+            .push(AttachmentField {
+                title: title.to_string(),
+                value: value.to_string(),
+                short,
+            });
+        self
+    }
+
+    pub fn image_url(mut self, url: &str) -> Self {
+        self.image_url = Some(url.to_string());
+        self
+    }
+
+    pub fn thumb_url(mut self, url: &str) -> Self {
+        self.thumb_url = Some(url.to_string());
+        self
+    }
+
+    pub fn footer(mut self, footer: &str, icon: Option<&str>) -> Self {
+        self.footer = Some(footer.to_string());
+        self.footer_icon = icon.map(str::to_string);
+        self
+    }
+
+    /// The time shown in the footer, as a UNIX timestamp
+    pub fn ts(mut self, ts: i64) -> Self {
+        self.ts = Some(ts);
+        self
+    }
+
+    pub fn blocks(mut self, blocks: Vec<Block>) -> Self {
+        self.blocks = Some(blocks);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn metadata_that_cant_be_serialized_is_an_error() {
+        let payload = HashMap::from([((1, 2), "value")]);
+        assert!(matches!(
+            Message::new().metadata("event", payload),
+            Err(SlackError::InvalidInput(_))
+        ));
+
+        let message = Message::new()
+            .metadata("event", HashMap::from([("key", "value")]))
+            .unwrap()
+            .build();
+        assert_eq!(message.metadata.unwrap().event_payload["key"], "value");
     }
 }
@@ -1,3 +1,6 @@
+pub mod blocks;
+pub mod bot;
 pub mod channel;
 pub mod message;
 pub mod user;
+pub mod view;
@@ -1,6 +1,21 @@
-use serde::{Serialize, Serializer};
+use std::{collections::HashMap, sync::Mutex, time::Duration};
 
-use crate::client::{Channel, ContextSupplier, Message, SlackApiResponse, SlackError};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tokio::time::Instant;
+
+use crate::client::{
+    api::{
+        chat, conversations,
+        users::{self, Presence},
+        views, Paginate,
+    },
+    model::{
+        message::PostedMessage,
+        view::{View, ViewInfo},
+    },
+    Channel, ContextSupplier, Message, SlackError,
+};
 
 #[derive(Default, Debug, Clone)]
 pub struct User {
@@ -16,44 +31,296 @@ impl Serialize for User {
     }
 }
 
+impl<'de> Deserialize<'de> for User {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(Self::new)
+    }
+}
+
 impl User {
     pub async fn send_dm(
         &self,
-        ctx: &impl ContextSupplier<'_>,
+        ctx: &impl ContextSupplier,
         mut msg: Message,
-    ) -> Result<(), SlackError> {
+    ) -> Result<PostedMessage, SlackError> {
         // technically not a channel but oh well
         msg.channel = Some(Channel {
             id: self.id.clone(),
         });
 
-        println!("{}", serde_json::to_string_pretty(&msg).unwrap());
+        let response = ctx
+            .get_context()
+            .api
+            .call(&chat::PostMessage { message: msg })
+            .await?;
+
+        // the response has the actual DM channel, rather than the user
+        Ok(PostedMessage::posted(response))
+    }
 
-        let response: SlackApiResponse = ctx
+    /// Shows the view in the Home tab of the app for this user, replacing what was there before
+    pub async fn publish_home(
+        &self,
+        ctx: &impl ContextSupplier,
+        view: View,
+    ) -> Result<ViewInfo, SlackError> {
+        let response = ctx
             .get_context()
-            .client
-            .post("https://slack.com/api/chat.postMessage")
-            // TODO: make into struct
-            .json(&msg)
-            .header(
-                "Authorization",
-                &("Bearer ".to_string() + &ctx.get_context().token),
-            )
-            .send()
-            .await
-            .unwrap()
-            .json()
-            .await
-            .unwrap();
-
-        if !response.ok {
-            return Err(SlackError::ApiError(response.error.unwrap()));
+            .api
+            .call(&views::Publish {
+                user_id: self.clone(),
+                hash: None,
+                view,
+            })
+            .await?;
+        Ok(response.view)
+    }
+
+    /// Looks up the names, timezone and profile of the user.
+    /// Results are cached for a while, and dropped when the user changes their profile.
+    pub async fn info(&self, ctx: &impl ContextSupplier) -> Result<UserInfo, SlackError> {
+        let ctx = ctx.get_context();
+        if let Some(info) = ctx.users.get(self) {
+            return Ok(info);
         }
 
-        Ok(())
+        let response = ctx
+            .api
+            .call(&users::Info {
+                user: self.clone(),
+                include_locale: None,
+            })
+            .await?;
+        ctx.users.insert(response.user.clone());
+        Ok(response.user)
+    }
+
+    /// Finds the user with this email address. Needs the `users:read.email` scope.
+    pub async fn lookup_by_email(
+        ctx: &impl ContextSupplier,
+        email: &str,
+    ) -> Result<UserInfo, SlackError> {
+        let ctx = ctx.get_context();
+        let response = ctx
+            .api
+            .call(&users::LookupByEmail {
+                email: email.to_string(),
+            })
+            .await?;
+        ctx.users.insert(response.user.clone());
+        Ok(response.user)
+    }
+
+    pub async fn presence(&self, ctx: &impl ContextSupplier) -> Result<Presence, SlackError> {
+        let response = ctx
+            .get_context()
+            .api
+            .call(&users::GetPresence { user: self.clone() })
+            .await?;
+        Ok(response.presence)
+    }
+
+    /// Opens the DM between the bot and the user, which is the channel `send_dm` posts in
+    pub async fn open_dm(&self, ctx: &impl ContextSupplier) -> Result<Channel, SlackError> {
+        let response = ctx
+            .get_context()
+            .api
+            .call(&conversations::Open {
+                users: self.id.clone(),
+            })
+            .await?;
+        Ok(response.channel.id)
+    }
+
+    /// Everyone in the workspace, including deactivated users and bots
+    pub fn list(ctx: &impl ContextSupplier) -> Paginate<users::List> {
+        ctx.get_context().api.paginate(users::List::default())
     }
 
-    pub(crate) fn new(id: String) -> Self {
+    pub fn new(id: String) -> Self {
         Self { id }
     }
 }
+
+/// A user as Slack describes them
+#[derive(Debug, Clone, Deserialize)]
+pub struct UserInfo {
+    pub id: User,
+    pub team_id: Option<String>,
+    /// The handle of the user, which is being phased out in favour of the display name
+    pub name: String,
+    pub real_name: Option<String>,
+    /// Whether the user has been deactivated
+    #[serde(default)]
+    pub deleted: bool,
+    /// The name of their timezone, like `Europe/London`
+    pub tz: Option<String>,
+    /// A readable name of their timezone, like `British Summer Time`
+    pub tz_label: Option<String>,
+    /// How many seconds their timezone is ahead of UTC
+    #[serde(default)]
+    pub tz_offset: i32,
+    #[serde(default)]
+    pub is_bot: bool,
+    #[serde(default)]
+    pub is_admin: bool,
+    #[serde(default)]
+    pub is_owner: bool,
+    /// Whether the user is a guest
+    #[serde(default)]
+    pub is_restricted: bool,
+    #[serde(default)]
+    pub profile: UserProfile,
+}
+
+impl UserInfo {
+    /// The name Slack shows for the user: their display name, or their real name if they
+    /// haven't set one
+    pub fn display_name(&self) -> &str {
+        [
+            self.profile.display_name.as_str(),
+            self.profile.real_name.as_str(),
+            self.real_name.as_deref().unwrap_or_default(),
+        ]
+        .into_iter()
+        .find(|name| !name.is_empty())
+        .unwrap_or(&self.name)
+    }
+
+    /// The timezone of the user, if Slack knows it
+    pub fn timezone(&self) -> Option<Tz> {
+        self.tz.as_deref()?.parse().ok()
+    }
+}
+
+/// What the user filled in about themselves
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct UserProfile {
+    pub display_name: String,
+    pub real_name: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub title: String,
+    pub phone: String,
+    /// Only there with the `users:read.email` scope
+    pub email: Option<String>,
+    pub status_text: String,
+    pub status_emoji: String,
+    pub image_72: Option<String>,
+    pub image_192: Option<String>,
+}
+
+/// Keeps what `users.info` returned for a while, so looking up the same user again doesn't
+/// need another call
+pub(crate) struct UserCache {
+    ttl: Duration,
+    users: Mutex<HashMap<String, (Instant, UserInfo)>>,
+}
+
+impl UserCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            users: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get(&self, user: &User) -> Option<UserInfo> {
+        let mut users = self.users.lock().unwrap();
+        match users.get(&user.id) {
+            Some((cached, info)) if cached.elapsed() < self.ttl => Some(info.clone()),
+            Some(_) => {
+                users.remove(&user.id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, info: UserInfo) {
+        if self.ttl.is_zero() {
+            return;
+        }
+
+        let mut users = self.users.lock().unwrap();
+
+        // users that aren't looked up again would otherwise stay forever
+        users.retain(|_, (cached, _)| cached.elapsed() < self.ttl);
+        users.insert(info.id.id.clone(), (Instant::now(), info));
+    }
+
+    pub(crate) fn invalidate(&self, user: &User) {
+        self.users.lock().unwrap().remove(&user.id);
+    }
+}
+
+impl std::fmt::Debug for UserCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserCache")
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(id: &str) -> UserInfo {
+        serde_json::from_value(serde_json::json!({ "id": id, "name": id })).unwrap()
+    }
+
+    fn cached(cache: &UserCache, id: &str) -> bool {
+        cache.get(&User::new(id.to_string())).is_some()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn entries_expire_after_the_ttl() {
+        let cache = UserCache::new(Duration::from_secs(60));
+        cache.insert(info("U1"));
+        assert!(cached(&cache, "U1"));
+
+        tokio::time::advance(Duration::from_secs(59)).await;
+        assert!(cached(&cache, "U1"));
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert!(!cached(&cache, "U1"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn inserting_drops_expired_entries() {
+        let cache = UserCache::new(Duration::from_secs(60));
+        cache.insert(info("U1"));
+        tokio::time::advance(Duration::from_secs(30)).await;
+        cache.insert(info("U2"));
+
+        tokio::time::advance(Duration::from_secs(30)).await;
+        cache.insert(info("U3"));
+        let users = cache.users.lock().unwrap();
+        assert!(!users.contains_key("U1"));
+        assert!(users.contains_key("U2") && users.contains_key("U3"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn invalidate_drops_the_user() {
+        let cache = UserCache::new(Duration::from_secs(60));
+        cache.insert(info("U1"));
+        cache.insert(info("U2"));
+
+        cache.invalidate(&User::new("U1".to_string()));
+        assert!(!cached(&cache, "U1"));
+        assert!(cached(&cache, "U2"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn zero_ttl_caches_nothing() {
+        let cache = UserCache::new(Duration::ZERO);
+        cache.insert(info("U1"));
+        assert!(!cached(&cache, "U1"));
+        assert!(cache.users.lock().unwrap().is_empty());
+    }
+}
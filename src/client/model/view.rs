@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::blocks::{check_length, validate_blocks, Block, PlainText, MAX_VIEW_BLOCKS};
+use crate::client::{Channel, SlackError, User};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViewType {
+    Modal,
+    Home,
+}
+
+/// A modal or App Home view
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize)]
+pub struct View {
+    #[serde(rename = "type")]
+    ty: ViewType,
+    title: Option<PlainText>,
+    submit: Option<PlainText>,
+    close: Option<PlainText>,
+    blocks: Vec<Block>,
+    callback_id: Option<String>,
+    private_metadata: Option<String>,
+    external_id: Option<String>,
+    clear_on_close: Option<bool>,
+    notify_on_close: Option<bool>,
+}
+
+impl View {
+    pub fn modal(title: &str) -> Self {
+        Self {
+            ty: ViewType::Modal,
+            title: Some(PlainText::new(title)),
+            submit: None,
+            close: None,
+            blocks: Vec::new(),
+            callback_id: None,
+            private_metadata: None,
+            external_id: None,
+            clear_on_close: None,
+            notify_on_close: None,
+        }
+    }
+
+    /// The App Home of a user, published with `User::publish_home`
+    pub fn home() -> Self {
+        Self {
+            ty: ViewType::Home,
+            title: None,
+            ..Self::modal("")
+        }
+    }
+
+    /// Used to find the `Modal` that handles submissions of this view
+    pub fn callback_id(mut self, callback_id: &str) -> Self {
+        self.callback_id = Some(callback_id.to_string());
+        self
+    }
+
+    /// The label of the submit button. Needed if the view has any inputs.
+    pub fn submit(mut self, submit: &str) -> Self {
+        self.submit = Some(PlainText::new(submit));
+        self
+    }
+
+    /// The label of the close button
+    pub fn close(mut self, close: &str) -> Self {
+        self.close = Some(PlainText::new(close));
+        self
+    }
+
+    pub fn blocks(mut self, blocks: Vec<Block>) -> Self {
+        self.blocks = blocks;
+        self
+    }
+
+    /// Data that's sent back with submissions, up to 3000 characters
+    pub fn private_metadata(mut self, private_metadata: &str) -> Self {
+        self.private_metadata = Some(private_metadata.to_string());
+        self
+    }
+
+    pub fn external_id(mut self, external_id: &str) -> Self {
+        self.external_id = Some(external_id.to_string());
+        self
+    }
+
+    /// Close every view in the stack when this one is closed
+    pub fn clear_on_close(mut self) -> Self {
+        self.clear_on_close = Some(true);
+        self
+    }
+
+    /// Send a `view_closed` event when the user closes the view
+    pub fn notify_on_close(mut self) -> Self {
+        self.notify_on_close = Some(true);
+        self
+    }
+
+    /// Checks the view against Slack's limits, which happens before it's sent
+    pub fn validate(&self) -> Result<(), SlackError> {
+        if let Some(title) = &self.title {
+            title.validate("view title", 24)?;
+        }
+        if let Some(submit) = &self.submit {
+            submit.validate("submit button", 24)?;
+        }
+        if let Some(close) = &self.close {
+            close.validate("close button", 24)?;
+        }
+        if let Some(private_metadata) = &self.private_metadata {
+            check_length("private_metadata", private_metadata, 3000)?;
+        }
+        validate_blocks(&self.blocks, MAX_VIEW_BLOCKS)
+    }
+}
+
+/// A view that's open in Slack, as sent with interactions
+#[derive(Debug, Clone, Deserialize)]
+pub struct ViewInfo {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub ty: ViewType,
+    pub hash: String,
+    #[serde(default)]
+    pub callback_id: String,
+    #[serde(default)]
+    pub private_metadata: String,
+    pub root_view_id: Option<String>,
+    pub previous_view_id: Option<String>,
+    #[serde(default)]
+    pub state: ViewState,
+}
+
+/// The values of the inputs in a view, by block ID and then action ID
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ViewState {
+    #[serde(default)]
+    pub values: HashMap<String, HashMap<String, InputValue>>,
+}
+
+impl ViewState {
+    pub fn get(&self, block_id: &str, action_id: &str) -> Option<&InputValue> {
+        self.values.get(block_id)?.get(action_id)
+    }
+
+    /// The text of a text, number, email or URL input
+    pub fn text(&self, block_id: &str, action_id: &str) -> Option<&str> {
+        self.get(block_id, action_id)?.text()
+    }
+
+    /// The value of the selected option of a select menu or radio buttons
+    pub fn selected_option(&self, block_id: &str, action_id: &str) -> Option<&str> {
+        self.get(block_id, action_id)?
+            .selected_options()
+            .first()
+            .map(|option| option.value.as_str())
+    }
+
+    /// The selected users of a user select
+    pub fn selected_users(&self, block_id: &str, action_id: &str) -> Vec<User> {
+        self.get(block_id, action_id)
+            .map(InputValue::selected_users)
+            .unwrap_or_default()
+    }
+
+    /// The selected conversations of a channel or conversation select
+    pub fn selected_channels(&self, block_id: &str, action_id: &str) -> Vec<Channel> {
+        self.get(block_id, action_id)
+            .map(InputValue::selected_channels)
+            .unwrap_or_default()
+    }
+}
+
+/// An option picked in a select menu, checkboxes or radio buttons
+#[derive(Debug, Clone, Deserialize)]
+pub struct SelectedOption {
+    pub value: String,
+    #[serde(deserialize_with = "text_of")]
+    pub text: String,
+}
+
+fn text_of<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Text {
+        text: String,
+    }
+
+    Text::deserialize(deserializer).map(|text| text.text)
+}
+
+/// The value of an interactive element, depending on its type
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InputValue {
+    Button {
+        value: Option<String>,
+    },
+    Overflow {
+        selected_option: Option<SelectedOption>,
+    },
+    PlainTextInput {
+        value: Option<String>,
+    },
+    NumberInput {
+        value: Option<String>,
+    },
+    EmailTextInput {
+        value: Option<String>,
+    },
+    UrlTextInput {
+        value: Option<String>,
+    },
+    StaticSelect {
+        selected_option: Option<SelectedOption>,
+    },
+    ExternalSelect {
+        selected_option: Option<SelectedOption>,
+    },
+    MultiStaticSelect {
+        #[serde(default)]
+        selected_options: Vec<SelectedOption>,
+    },
+    MultiExternalSelect {
+        #[serde(default)]
+        selected_options: Vec<SelectedOption>,
+    },
+    UsersSelect {
+        selected_user: Option<User>,
+    },
+    MultiUsersSelect {
+        #[serde(default)]
+        selected_users: Vec<User>,
+    },
+    ConversationsSelect {
+        selected_conversation: Option<Channel>,
+    },
+    MultiConversationsSelect {
+        #[serde(default)]
+        selected_conversations: Vec<Channel>,
+    },
+    ChannelsSelect {
+        selected_channel: Option<Channel>,
+    },
+    MultiChannelsSelect {
+        #[serde(default)]
+        selected_channels: Vec<Channel>,
+    },
+    Datepicker {
+        selected_date: Option<String>,
+    },
+    Timepicker {
+        selected_time: Option<String>,
+    },
+    Datetimepicker {
+        selected_date_time: Option<i64>,
+    },
+    Checkboxes {
+        #[serde(default)]
+        selected_options: Vec<SelectedOption>,
+    },
+    RadioButtons {
+        selected_option: Option<SelectedOption>,
+    },
+    /// An element crow doesn't know about yet
+    #[serde(other)]
+    Unknown,
+}
+
+impl InputValue {
+    /// The text of a text, number, email or URL input, or the date or time of a picker
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            InputValue::PlainTextInput { value }
+            | InputValue::NumberInput { value }
+            | InputValue::EmailTextInput { value }
+            | InputValue::UrlTextInput { value } => value.as_deref(),
+            InputValue::Datepicker { selected_date } => selected_date.as_deref(),
+            InputValue::Timepicker { selected_time } => selected_time.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn selected_options(&self) -> Vec<&SelectedOption> {
+        match self {
+            InputValue::StaticSelect { selected_option }
+            | InputValue::ExternalSelect { selected_option }
+            | InputValue::RadioButtons { selected_option }
+            | InputValue::Overflow { selected_option } => selected_option.iter().collect(),
+            InputValue::MultiStaticSelect { selected_options }
+            | InputValue::MultiExternalSelect { selected_options }
+            | InputValue::Checkboxes { selected_options } => selected_options.iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn selected_users(&self) -> Vec<User> {
+        match self {
+            InputValue::UsersSelect { selected_user } => selected_user.iter().cloned().collect(),
+            InputValue::MultiUsersSelect { selected_users } => selected_users.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn selected_channels(&self) -> Vec<Channel> {
+        match self {
+            InputValue::ConversationsSelect {
+                selected_conversation: selected,
+            }
+            | InputValue::ChannelsSelect {
+                selected_channel: selected,
+            } => selected.iter().cloned().collect(),
+            InputValue::MultiConversationsSelect {
+                selected_conversations: selected,
+            }
+            | InputValue::MultiChannelsSelect {
+                selected_channels: selected,
+            } => selected.clone(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::model::blocks::Divider;
+
+    fn is_invalid(view: View) -> bool {
+        matches!(view.validate(), Err(SlackError::InvalidInput(_)))
+    }
+
+    #[test]
+    fn titles_and_buttons_fit_in_24_characters() {
+        let long = "a".repeat(25);
+        assert!(View::modal(&"a".repeat(24))
+            .submit(&"a".repeat(24))
+            .close(&"a".repeat(24))
+            .validate()
+            .is_ok());
+
+        assert!(is_invalid(View::modal(&long)));
+        assert!(is_invalid(View::modal("Title").submit(&long)));
+        assert!(is_invalid(View::modal("Title").close(&long)));
+    }
+
+    #[test]
+    fn private_metadata_fits_in_3000_characters() {
+        assert!(View::modal("Title")
+            .private_metadata(&"a".repeat(3000))
+            .validate()
+            .is_ok());
+        assert!(is_invalid(
+            View::modal("Title").private_metadata(&"a".repeat(3001))
+        ));
+    }
+
+    #[test]
+    fn views_can_have_100_blocks() {
+        let blocks = |count| (0..count).map(|_| Divider::new().into()).collect();
+        assert!(View::home().blocks(blocks(100)).validate().is_ok());
+        assert!(is_invalid(View::home().blocks(blocks(101))));
+    }
+}
@@ -0,0 +1,354 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use futures::{future::BoxFuture, FutureExt};
+use reqwest::Client;
+use serde::Serialize;
+use tokio::{sync::oneshot, time::Instant};
+
+use super::{interactions::InteractionContext, Message, SlackError};
+
+/// How long Slack accepts responses on a `response_url`
+const RESPONSE_URL_LIFETIME: Duration = Duration::from_secs(30 * 60);
+/// How many responses Slack accepts on a `response_url`
+const RESPONSE_URL_USES: usize = 5;
+
+/// Any error a handler can return
+pub type HandlerError = Box<dyn std::error::Error + Send + Sync>;
+
+/// What a command or action handler finished with.
+/// `Ok(Some(..))` is sent back to the user as the response.
+pub type HandlerResult = Result<Option<Reply>, HandlerError>;
+
+/// Who can see a reply
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseType {
+    /// Only the user who triggered the interaction
+    #[default]
+    Ephemeral,
+    /// Everyone in the channel
+    InChannel,
+}
+
+/// A response to a command or interaction.
+///
+/// If a slash command finishes before Slack's 3 second deadline this is sent straight back as the
+/// response to the command, otherwise it is posted to the `response_url`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Reply {
+    pub response_type: ResponseType,
+    #[serde(flatten)]
+    pub message: Message,
+    /// Replace the message the interaction came from, instead of sending a new one
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub replace_original: bool,
+    /// Delete the message the interaction came from
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub delete_original: bool,
+}
+
+impl Reply {
+    /// A reply only the user who triggered the interaction can see
+    pub fn ephemeral(message: impl Into<Message>) -> Self {
+        Self {
+            response_type: ResponseType::Ephemeral,
+            message: message.into(),
+            replace_original: false,
+            delete_original: false,
+        }
+    }
+
+    /// A reply everyone in the channel can see
+    pub fn in_channel(message: impl Into<Message>) -> Self {
+        Self {
+            response_type: ResponseType::InChannel,
+            ..Self::ephemeral(message)
+        }
+    }
+
+    /// Deletes the message the interaction came from.
+    /// Only works through a `response_url`.
+    pub fn delete() -> Self {
+        Self {
+            delete_original: true,
+            ..Self::ephemeral("")
+        }
+    }
+
+    /// Replaces the message the interaction came from with this one.
+    /// Only works through a `response_url`.
+    pub fn replace_original(mut self) -> Self {
+        self.replace_original = true;
+        self
+    }
+}
+
+impl<T> From<T> for Reply
+where
+    T: Into<Message>,
+{
+    fn from(message: T) -> Self {
+        Reply::ephemeral(message)
+    }
+}
+
+/// Where responses to an interaction can be posted without any scopes.
+/// Slack only accepts 5 responses within 30 minutes of the interaction.
+#[derive(Debug)]
+pub struct ResponseUrl {
+    url: String,
+    received: Instant,
+    uses: AtomicUsize,
+}
+
+impl ResponseUrl {
+    pub(crate) fn new(url: String) -> Self {
+        Self {
+            url,
+            received: Instant::now(),
+            uses: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.received.elapsed() >= RESPONSE_URL_LIFETIME
+    }
+
+    /// How many more responses Slack will accept, or 0 if it has expired
+    pub fn remaining_uses(&self) -> usize {
+        if self.is_expired() {
+            return 0;
+        }
+
+        RESPONSE_URL_USES.saturating_sub(self.uses.load(Ordering::SeqCst))
+    }
+
+    pub(crate) async fn post(&self, client: &Client, reply: &Reply) -> Result<(), SlackError> {
+        reply.message.validate()?;
+
+        if self.is_expired() {
+            return Err(SlackError::InvalidInput(
+                "response_url has expired".to_string(),
+            ));
+        }
+
+        if self
+            .uses
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |uses| {
+                (uses < RESPONSE_URL_USES).then_some(uses + 1)
+            })
+            .is_err()
+        {
+            return Err(SlackError::InvalidInput(
+                "response_url has been used too many times".to_string(),
+            ));
+        }
+
+        let response = client
+            .post(&self.url)
+            .json(reply)
+            .send()
+            .await
+            .map_err(SlackError::Transport)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.map_err(SlackError::Transport)?;
+            return Err(SlackError::Http { status, body });
+        }
+
+        Ok(())
+    }
+}
+
+/// Hands a reply back to the listener, so it can be used as the body of the HTTP response.
+/// Only the first reply can be sent this way, and only until the listener gives up waiting.
+pub(crate) struct Responder<T = Option<Reply>>(Arc<Mutex<Option<oneshot::Sender<T>>>>);
+
+impl<T> Responder<T> {
+    pub(crate) fn new() -> (Self, oneshot::Receiver<T>) {
+        let (sender, receiver) = oneshot::channel();
+        (Self(Arc::new(Mutex::new(Some(sender)))), receiver)
+    }
+
+    /// Sends the reply to the listener, giving it back if the listener isn't waiting anymore
+    pub(crate) fn send(&self, reply: T) -> Result<(), T> {
+        match self.0.lock().unwrap().take() {
+            Some(sender) => sender.send(reply),
+            None => Err(reply),
+        }
+    }
+
+    /// Stops any more replies from being sent this way
+    pub(crate) fn close(&self) {
+        self.0.lock().unwrap().take();
+    }
+}
+
+impl<T> Clone for Responder<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Default for Responder<T> {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(None)))
+    }
+}
+
+impl<T> std::fmt::Debug for Responder<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Responder")
+            .field(&self.0.lock().unwrap().is_some())
+            .finish()
+    }
+}
+
+/// Called when a command or action handler returns an error
+pub type ErrorHandlerFn = fn(InteractionContext, HandlerError) -> BoxFuture<'static, ()>;
+
+/// Something a command or action handler can return.
+///
+/// Text and messages are sent to the user as the response, `()` sends nothing, and errors in a
+/// `Result` are passed to the error handler set on the `SlackClientBuilder`.
+pub trait IntoResponse {
+    fn into_response(self) -> HandlerResult;
+}
+
+impl IntoResponse for () {
+    fn into_response(self) -> HandlerResult {
+        Ok(None)
+    }
+}
+
+impl IntoResponse for Reply {
+    fn into_response(self) -> HandlerResult {
+        Ok(Some(self))
+    }
+}
+
+/// Sent as an ephemeral reply
+impl IntoResponse for Message {
+    fn into_response(self) -> HandlerResult {
+        Ok(Some(Reply::ephemeral(self)))
+    }
+}
+
+/// Sent as an ephemeral reply
+impl IntoResponse for String {
+    fn into_response(self) -> HandlerResult {
+        Ok(Some(Reply::ephemeral(self)))
+    }
+}
+
+/// Sent as an ephemeral reply
+impl IntoResponse for &str {
+    fn into_response(self) -> HandlerResult {
+        Ok(Some(Reply::ephemeral(self)))
+    }
+}
+
+impl<T> IntoResponse for Option<T>
+where
+    T: IntoResponse,
+{
+    fn into_response(self) -> HandlerResult {
+        match self {
+            Some(response) => response.into_response(),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T, E> IntoResponse for Result<T, E>
+where
+    T: IntoResponse,
+    E: Into<HandlerError>,
+{
+    fn into_response(self) -> HandlerResult {
+        match self {
+            Ok(response) => response.into_response(),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Logs the error and lets the user know something went wrong
+pub fn default_error_handler(
+    ctx: InteractionContext,
+    error: HandlerError,
+) -> BoxFuture<'static, ()> {
+    async move {
+        println!("Error while handling {}: {}", describe(&ctx), error);
+
+        if let Err(err) = ctx
+            .reply(Message::from(
+                "Sorry, something went wrong while doing that. Please try again later.",
+            ))
+            .await
+        {
+            println!("Failed to tell the user about the error: {:?}", err);
+        }
+    }
+    .boxed()
+}
+
+fn describe(ctx: &InteractionContext) -> String {
+    if ctx.command.is_empty() {
+        format!("an interaction from <@{}>", ctx.user.id)
+    } else {
+        format!("{} from <@{}>", ctx.command, ctx.user.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::api::tests::mock_slack;
+
+    #[tokio::test(start_paused = true)]
+    async fn response_urls_can_be_used_five_times() {
+        let mock = mock_slack().await;
+        let client = Client::new();
+        let url = ResponseUrl::new(format!("http://{}/ok/response", mock.addr));
+        let reply = Reply::ephemeral("caw");
+
+        for remaining in (1..=5).rev() {
+            assert_eq!(url.remaining_uses(), remaining);
+            url.post(&client, &reply).await.unwrap();
+        }
+
+        assert_eq!(url.remaining_uses(), 0);
+        assert!(matches!(
+            url.post(&client, &reply).await,
+            Err(SlackError::InvalidInput(_))
+        ));
+        assert_eq!(mock.requests().len(), 5);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn response_urls_expire_after_30_minutes() {
+        let mock = mock_slack().await;
+        let url = ResponseUrl::new(format!("http://{}/ok/response", mock.addr));
+
+        tokio::time::advance(RESPONSE_URL_LIFETIME).await;
+        assert!(url.is_expired());
+        assert_eq!(url.remaining_uses(), 0);
+        assert!(matches!(
+            url.post(&Client::new(), &Reply::ephemeral("caw")).await,
+            Err(SlackError::InvalidInput(_))
+        ));
+        assert!(mock.requests().is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+use futures::future::BoxFuture;
+use serde::Serialize;
+
+use super::{
+    interactions::InteractionContext,
+    model::{
+        blocks::{OptionGroup, SelectOption},
+        view::ViewInfo,
+    },
+};
+
+/// How many options, or option groups, Slack accepts in a response
+const MAX_SUGGESTIONS: usize = 100;
+
+/// Called with what the user has typed so far, and has to answer within 3 seconds
+pub type OptionsFn = fn(InteractionContext, OptionsQuery) -> BoxFuture<'static, Suggestions>;
+
+/// Fills an `external_select` with options from our own code.
+///
+/// ```ignore
+/// fn projects(ctx: InteractionContext, query: OptionsQuery) -> BoxFuture<'static, Suggestions> {
+///     async move {
+///         search_projects(&query.value)
+///             .await
+///             .into_iter()
+///             .map(|project| SelectOption::new(&project.name, &project.key))
+///             .collect()
+///     }
+///     .boxed()
+/// }
+///
+/// SlackClient::new().options_provider(OptionsProvider::new("jira_project", projects))
+/// ```
+pub struct OptionsProvider {
+    pub action_id: String,
+    pub execute: OptionsFn,
+}
+
+impl OptionsProvider {
+    pub fn new(action_id: &str, execute: OptionsFn) -> Self {
+        Self {
+            action_id: action_id.to_string(),
+            execute,
+        }
+    }
+}
+
+/// What Slack asked for options with
+#[derive(Debug, Clone)]
+pub struct OptionsQuery {
+    pub action_id: String,
+    pub block_id: String,
+    /// What the user has typed so far
+    pub value: String,
+    /// The view the select is in, if it's in a modal or the App Home
+    pub view: Option<ViewInfo>,
+}
+
+/// The options to show, either all together or in groups
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum Suggestions {
+    Options { options: Vec<SelectOption> },
+    Groups { option_groups: Vec<OptionGroup> },
+}
+
+impl Suggestions {
+    /// Drops everything over Slack's limits, which would otherwise make it reject the whole
+    /// response and show an empty menu
+    pub(crate) fn truncate(&mut self) {
+        match self {
+            Suggestions::Options { options } => options.truncate(MAX_SUGGESTIONS),
+            Suggestions::Groups { option_groups } => {
+                option_groups.truncate(MAX_SUGGESTIONS);
+                for group in option_groups {
+                    group.truncate(MAX_SUGGESTIONS);
+                }
+            }
+        }
+    }
+}
+
+impl Default for Suggestions {
+    fn default() -> Self {
+        Suggestions::Options {
+            options: Vec::new(),
+        }
+    }
+}
+
+impl From<Vec<SelectOption>> for Suggestions {
+    fn from(options: Vec<SelectOption>) -> Self {
+        Suggestions::Options { options }
+    }
+}
+
+impl From<Vec<OptionGroup>> for Suggestions {
+    fn from(option_groups: Vec<OptionGroup>) -> Self {
+        Suggestions::Groups { option_groups }
+    }
+}
+
+impl FromIterator<SelectOption> for Suggestions {
+    fn from_iter<T: IntoIterator<Item = SelectOption>>(iter: T) -> Self {
+        Suggestions::Options {
+            options: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::to_value;
+
+    use super::*;
+
+    fn options(count: usize) -> Vec<SelectOption> {
+        (0..count)
+            .map(|i| SelectOption::new("option", &i.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn options_are_cut_at_100() {
+        let mut suggestions = Suggestions::from(options(150));
+        suggestions.truncate();
+
+        let value = to_value(&suggestions).unwrap();
+        assert_eq!(value["options"].as_array().unwrap().len(), 100);
+        assert_eq!(value["options"][99]["value"], "99");
+    }
+
+    #[test]
+    fn option_groups_are_cut_at_100() {
+        let mut suggestions = Suggestions::from(
+            (0..101)
+                .map(|_| OptionGroup::new("group", options(101)))
+                .collect::<Vec<_>>(),
+        );
+        suggestions.truncate();
+
+        let value = to_value(&suggestions).unwrap();
+        let groups = value["option_groups"].as_array().unwrap();
+        assert_eq!(groups.len(), 100);
+        assert!(groups
+            .iter()
+            .all(|group| group["options"].as_array().unwrap().len() == 100));
+    }
+}
@@ -95,6 +95,7 @@ impl Cron {
     }
 }
 
+#[allow(dead_code, clippy::enum_variant_names)]
 #[derive(Debug)]
 pub enum CronError {
     InvalidRange(String),
@@ -113,10 +114,7 @@ enum CronValue {
 
 impl CronValue {
     pub(super) fn is_alternative(&self) -> bool {
-        match self {
-            CronValue::Alternative(_) => true,
-            _ => false,
-        }
+        matches!(self, CronValue::Alternative(_))
     }
 
     fn parse_part(part: &str) -> Result<CronValue, CronError> {
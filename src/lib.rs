@@ -0,0 +1,27 @@
+// lets the macros refer to `::crow` from inside this crate too
+extern crate self as crow;
+
+#[macro_use]
+pub mod client;
+
+pub use crow_derive::{global_shortcut, message_action, message_shortcut, slash_command, Argument};
+
+/// Everything the code generated by `crow_derive` refers to.
+/// Not part of the public API, use the paths in `client` instead.
+#[doc(hidden)]
+pub mod __private {
+    pub use futures::future::BoxFuture;
+    pub use futures::FutureExt;
+    pub use regex::Regex;
+
+    pub use crate::client::{
+        arguments::{report_usage_error, usage_of, Argument, ArgumentError, Arguments},
+        events::{BlockAction, ShortcutMessage},
+        interactions::{
+            ActionId, GlobalShortcut, InteractionContext, MessageAction, MessageShortcut,
+            SlashCommand,
+        },
+        response::{HandlerResult, IntoResponse},
+        Channel, User,
+    };
+}
@@ -1,21 +1,14 @@
-use std::future::Future;
-use std::pin::Pin;
-use std::str::FromStr;
 use std::time::Duration;
 
-use chrono::TimeZone;
-use chrono::{Local, Timelike, Utc};
+use chrono::{TimeZone, Timelike, Utc};
 use chrono_tz::Europe::London;
-use client::events::BlockMessageInteractionEvent;
-use client::interactions::{CommandFn, InteractionContext, SlashCommand};
-use client::{Channel, Context, EventHandler, Message, SlackClient, User};
 use cron::Cron;
-use crow_derive::{message_action, slash_command};
-use futures::future::BoxFuture;
-use futures::FutureExt;
+use crow::client::arguments::Rest;
+use crow::client::interactions::InteractionContext;
+use crow::client::model::blocks::{Actions, Button, Section, Style};
+use crow::client::{Channel, Context, EventHandler, Message, SlackClient, SlackError, User};
+use crow::{message_action, slash_command};
 
-#[macro_use]
-mod client;
 mod cron;
 
 pub struct CronEvent {
@@ -43,7 +36,7 @@ struct Handler {
 impl EventHandler for Handler {
     async fn member_joined_channel(
         &self,
-        ctx: Context<'_>,
+        ctx: Context,
         channel: Channel,
         user: User,
         _inviter: Option<User>,
@@ -64,10 +57,27 @@ impl EventHandler for Handler {
             .await
             .unwrap();
 
+        // greet them by name, at the right time o' day fer wherever they be
+        let greeting = match user.info(&ctx).await {
+            Ok(info) => {
+                let hour = info
+                    .timezone()
+                    .map(|tz| Utc::now().with_timezone(&tz).hour());
+                let salute = match hour {
+                    Some(5..=11) => "Mornin'",
+                    Some(12..=17) => "Afternoon",
+                    Some(_) => "Evenin'",
+                    None => "Ahoy",
+                };
+                format!("{}, {}! ", salute, info.display_name())
+            }
+            Err(_) => String::new(),
+        };
+
         user.send_dm(
             &ctx,
             Message::new()
-                .text("Arrr, *welcome to the cove, landlubber*! Ye be joining a place fer only the _boldest o' buccaneers_!".to_string())
+                .text(format!("{}Arrr, *welcome to the cove, landlubber*! Ye be joining a place fer only the _boldest o' buccaneers_!", greeting))
                 .build(),
         ).await.unwrap();
         tokio::time::sleep(Duration::from_secs(1)).await;
@@ -81,12 +91,21 @@ impl EventHandler for Handler {
         user.send_dm(
             &ctx,
             Message::new()
-                .blocks(r#"[{"type": "section","text": {"type": "mrkdwn","text": "Now, throw off yer landlubber ways, grab yerself a tankard o' *rum*, and *let's hear yer boldest tale*! A final question for ye - _do ye wish t' introduce yourself t' the crew?_"}},{"type": "actions","elements": [{"type": "button","style": "primary","text": {"type": "plain_text","text": "announce yer presence","emoji": true},"value": "announce_presence"}]}]"#.to_string())
+                .blocks(vec![
+                    Section::new("Now, throw off yer landlubber ways, grab yerself a tankard o' *rum*, and *let's hear yer boldest tale*! A final question for ye - _do ye wish t' introduce yourself t' the crew?_").into(),
+                    Actions::new(vec![
+                        Button::new("announce yer presence", "announce_presence")
+                            .style(Style::Primary)
+                            .value("announce_presence")
+                            .into(),
+                    ])
+                    .into(),
+                ])
                 .build(),
         ).await.unwrap();
     }
 
-    async fn callback(&self, ctx: &Context<'_>) {
+    async fn callback(&self, ctx: &Context) {
         // runs every 60 seconds
         //println!("callback");
         let utc = Utc::now().naive_utc();
@@ -131,8 +150,9 @@ impl EventHandler for Handler {
     }*/
 }
 
-/*#[slash_command]
-async fn crow(ctx: InteractionContext<'_>, text: String, user: User, channel: Channel) {
+/*/// Says hello
+#[slash_command]
+async fn crow(ctx: InteractionContext, text: String, user: User, channel: Channel) {
     println!(
         "Recieved a command from <@{}> in <#{}> with text {}.",
         user.id, channel.id, text
@@ -150,7 +170,7 @@ async fn crow(ctx: InteractionContext<'_>, text: String, user: User, channel: Ch
 
 /*fn crow() -> crate::client::interactions::SlashCommand {
     fn inner(
-        ctx: InteractionContext<'_>,
+        ctx: InteractionContext,
         text: String,
         user: User,
         channel: Channel,
@@ -177,31 +197,25 @@ async fn crow(ctx: InteractionContext<'_>, text: String, user: User, channel: Ch
     }
 }*/
 
+/// Says hello
 #[slash_command]
-async fn crow(ctx: InteractionContext<'_>, text: String, user: User, channel: Channel) {
+async fn crow(ctx: InteractionContext, text: Rest) -> &'static str {
     println!(
         "Recieved a command from <@{}> in <#{}> with text {}.",
-        user.id, channel.id, text
+        ctx.user.id, ctx.channel.id, text
     );
 
-    channel
-        .post_message(
-            &ctx,
-            Message::new().text("Hello, world!".to_string()).build(),
-            Some(user),
-        )
-        .await
-        .unwrap();
+    "Hello, world!"
 }
 
 #[message_action]
 async fn announce_presence(
-    ctx: InteractionContext<'_>,
+    ctx: InteractionContext,
     user: User,
-    name: String,
-    display_name: String,
-    channel: Channel,
-) {
+    _name: String,
+    _display_name: String,
+    _channel: Channel,
+) -> Result<(), SlackError> {
     Channel::new("C07DWPZ01K6".to_string())
         .post_message(
             &ctx,
@@ -213,8 +227,9 @@ async fn announce_presence(
                 .build(),
             None,
         )
-        .await
-        .unwrap();
+        .await?;
+
+    Ok(())
 }
 
 /*async fn pigeon(mut ctx: InteractionContext, channel: Channel, user: User) -> InteractionContext {